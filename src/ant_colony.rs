@@ -0,0 +1,226 @@
+use rand::Rng;
+
+use crate::cell_set::CellSet;
+use crate::io::Input;
+use crate::metric::PositionSet;
+use crate::obstacles::Distances;
+use crate::quantum::QPos;
+use crate::{score, Action, ActionKind};
+
+
+const NEIGHBOURS: usize = 10;
+const ALPHA: f64 = 1.0;
+const BETA: f64 = 2.0;
+const EVAPORATION: f64 = 0.1;
+const MIN_PHEROMONE: f64 = 0.01;
+
+
+// Pheromone on the transitions to the nearest nodes only, so that it stays linear in the number of nodes.
+// Nodes are the plants, then the seeds, then the start.
+struct Pheromone
+{
+	lists: Vec<Vec<(usize, f64)>>,
+}
+
+impl Pheromone
+{
+	fn new(distances: &Distances, nodes: &[[i32; 2]]) -> Self
+	{
+		// The start is only left, never reached
+		let targets = nodes.len() - 1;
+
+		let lists = nodes.iter()
+			.enumerate()
+			.map(|(from_index, &from)|
+			{
+				let mut nearest: Vec<(i32, usize)> = (0..targets)
+					.filter(|&to| to != from_index)
+					.map(|to| (distances.distance(from, nodes[to]), to))
+					.collect();
+
+				let count = NEIGHBOURS.min(nearest.len());
+				if count > 0 && count < nearest.len()
+				{
+					nearest.select_nth_unstable(count - 1);
+				}
+				nearest.truncate(count);
+				nearest.sort_unstable();
+
+				nearest.into_iter().map(|(_, to)| (to, 1.0)).collect()
+			})
+			.collect();
+
+		Pheromone { lists }
+	}
+
+	fn evaporate(&mut self)
+	{
+		for (_, pheromone) in self.lists.iter_mut().flatten()
+		{
+			*pheromone = (*pheromone * (1.0 - EVAPORATION)).max(MIN_PHEROMONE);
+		}
+	}
+
+	fn deposit(&mut self, path: &[usize], amount: f64)
+	{
+		for window in path.windows(2)
+		{
+			if let Some((_, pheromone)) = self.lists[window[0]].iter_mut().find(|(node, _)| *node == window[1])
+			{
+				*pheromone += amount;
+			}
+		}
+	}
+}
+
+
+// One ant: it plants while it has seeds and collects when it has none, without going over the distance budget.
+// The next node is drawn among the nearest ones by pheromone^ALPHA × (1 / cost)^BETA, the nearest node
+// overall is taken when none of them can be done. Returns the nodes visited from the start.
+fn build<P: PositionSet, R: Rng>(input: &Input, nodes: &[[i32; 2]], pheromone: &Pheromone, rng: &mut R) -> Vec<usize>
+{
+	let plant_count = input.plants.len();
+	let start = nodes.len() - 1;
+
+	let mut done = vec![false; nodes.len()];
+	let mut pos = P::from_cell(input.start);
+	let mut seed_storage = input.seed_capacity;
+	let mut distance_traveled = 0;
+
+	let mut path = vec![start];
+
+	loop
+	{
+		let current = *path.last().unwrap();
+
+		let targets = if seed_storage > 0 { 0..plant_count } else { plant_count..start };
+
+		// Position and distance after going to the node, when the robot can still come back in time
+		let step = |to: usize| -> Option<(P, i32)>
+		{
+			if done[to] || !targets.contains(&to)
+			{
+				return None;
+			}
+
+			let (new_pos, dist) = if to < plant_count { pos.apply_plant(input, nodes[to]) } else { pos.apply_seed(input, nodes[to]) };
+
+			(distance_traveled + dist + new_pos.return_distance(input) <= input.max_distance as i32).then_some((new_pos, dist))
+		};
+
+		let candidates: Vec<(usize, f64)> = pheromone.lists[current].iter()
+			.filter_map(|&(to, level)|
+			{
+				let (_, dist) = step(to)?;
+				Some((to, level.powf(ALPHA) * (1.0 / (dist as f64 + 1.0)).powf(BETA)))
+			})
+			.collect();
+
+		let next = if candidates.is_empty()
+		{
+			targets.clone()
+				.filter_map(|to| Some((step(to)?.1, to)))
+				.min()
+				.map(|(_, to)| to)
+		}
+		else
+		{
+			let total: f64 = candidates.iter().map(|&(_, weight)| weight).sum();
+			let mut draw = rng.random_range(0.0..total);
+
+			candidates.iter()
+				.find(|&&(_, weight)|
+				{
+					draw -= weight;
+					draw < 0.0
+				})
+				.or(candidates.last())
+				.map(|&(to, _)| to)
+		};
+
+		let Some(next) = next
+		else
+		{
+			break;
+		};
+
+		let (new_pos, dist) = step(next).unwrap();
+		pos = new_pos;
+		distance_traveled += dist;
+		done[next] = true;
+
+		if next < plant_count
+		{
+			seed_storage -= 1;
+		}
+		else
+		{
+			seed_storage = input.seed_capacity;
+		}
+
+		path.push(next);
+	}
+
+	path
+}
+
+fn actions(input: &Input, nodes: &[[i32; 2]], path: &[usize]) -> Vec<Action>
+{
+	path[1..].iter()
+		.map(|&node| Action
+		{
+			pos: nodes[node],
+			kind: if node < input.plants.len() { ActionKind::Plant } else { ActionKind::Collect },
+		})
+		.collect()
+}
+
+
+// Ant colony optimization, the best ant of each iteration and the best one so far lay pheromone
+// in proportion to the value they planted per distance, the best one so far lays 1
+pub fn ant_colony<R: Rng>(input: &Input, ants: usize, iterations: usize, rng: &mut R) -> Vec<Action>
+{
+	let build = if input.exact_qpos() { build::<QPos, R> } else { build::<CellSet, R> };
+
+	let nodes: Vec<[i32; 2]> = input.plants.iter()
+		.chain(&input.seeds)
+		.copied()
+		.chain([input.start])
+		.collect();
+
+	let mut pheromone = Pheromone::new(&Distances::new(input), &nodes);
+
+	let mut best: Option<((u32, i32), Vec<usize>)> = None;
+
+	for _iteration in 0..iterations
+	{
+		let Some((iteration_score, iteration_best)) = (0..ants.max(1))
+			.map(|_|
+			{
+				let path = build(input, &nodes, &pheromone, rng);
+				(score(input, &actions(input, &nodes, &path)), path)
+			})
+			.max_by_key(|(score, _)| *score)
+		else
+		{
+			break;
+		};
+
+		if best.as_ref().is_none_or(|(best_score, _)| iteration_score > *best_score)
+		{
+			best = Some((iteration_score, iteration_best.clone()));
+		}
+
+		pheromone.evaporate();
+
+		let (best_score, best_path) = best.as_ref().unwrap();
+		let ratio = |(value, neg_distance): (u32, i32)| (value as f64 + 1.0) / (1.0 - neg_distance as f64);
+
+		for (score, path) in [(iteration_score, &iteration_best), (*best_score, best_path)]
+		{
+			pheromone.deposit(path, ratio(score) / ratio(*best_score));
+		}
+	}
+
+	best.map(|(_, path)| actions(input, &nodes, &path)).unwrap_or_default()
+}
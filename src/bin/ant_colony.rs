@@ -0,0 +1,19 @@
+use codingup_qualifs::ant_colony::ant_colony;
+use codingup_qualifs::io::read_input;
+use codingup_qualifs::solve_and_write_output;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn main() -> serde_json::Result<()>
+{
+	let ants: usize = std::env::args().nth(2).unwrap().parse().unwrap();
+	let iterations: usize = std::env::args().nth(3).unwrap().parse().unwrap();
+
+	let input = read_input()?;
+
+	let moves = ant_colony(&input, ants, iterations, &mut StdRng::seed_from_u64(0));
+
+	solve_and_write_output(&input, &moves);
+
+	Ok(())
+}
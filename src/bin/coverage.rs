@@ -0,0 +1,15 @@
+use codingup_qualifs::coverage::{route_stops, stops};
+use codingup_qualifs::io::read_input;
+use codingup_qualifs::solve_and_write_output;
+
+fn main()
+{
+	let input = read_input().unwrap();
+
+	let stops = stops(&input);
+	println!("{} plants in {} stops", input.plants.len(), stops.len());
+
+	let actions = route_stops(&input, &stops);
+
+	solve_and_write_output(&input, &actions);
+}
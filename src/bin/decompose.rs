@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use codingup_qualifs::decompose::decompose;
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::read_input;
+use codingup_qualifs::online::{rolling_horizon, Scenario};
+use codingup_qualifs::{pruning, solve_and_write_output};
+
+
+fn main()
+{
+	let input = read_input().unwrap();
+
+	let cluster_count = std::env::args().nth(2).unwrap().parse().unwrap();
+	// Inner solver of each cluster: `greedy`, `limited <depth>`, `pruning` or `rolling <seconds for the whole instance>`
+	let solver = std::env::args().nth(3).unwrap_or_else(|| "greedy".to_string());
+	let parameter = std::env::args().nth(4);
+
+	let actions = match solver.as_str()
+	{
+		"greedy" => decompose(&input, cluster_count, nearest_neighbour),
+		"limited" =>
+		{
+			let depth = parameter.unwrap().parse().unwrap();
+			decompose(&input, cluster_count, |cluster_input| limited_search(cluster_input, depth))
+		},
+		"pruning" => decompose(&input, cluster_count, |cluster_input| pruning::solve(cluster_input, cluster_input.max_distance as i32)),
+		"rolling" =>
+		{
+			let time_limit: f32 = parameter.unwrap().parse().unwrap();
+			let time_per_action = Duration::from_secs_f32(time_limit / input.plants.len() as f32);
+
+			decompose(&input, cluster_count, |cluster_input| rolling_horizon(cluster_input, &Scenario::default(), time_per_action))
+		},
+		_ => panic!("Unknown inner solver {solver}"),
+	};
+
+	let Some(actions) = actions
+	else
+	{
+		eprintln!("The decomposition does not support species, pile sizes or time windows");
+		return;
+	};
+
+	solve_and_write_output(&input, &actions);
+}
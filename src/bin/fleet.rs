@@ -0,0 +1,38 @@
+use codingup_qualifs::fleet::{resolve_robot, solve};
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::{read_input, write_fleet_output};
+use codingup_qualifs::planted_value;
+
+
+fn main()
+{
+	let input = read_input().unwrap();
+
+	// Optional lookahead depth of the constructor used for each robot
+	let depth: Option<u32> = std::env::args().nth(2).map(|arg| arg.parse().unwrap());
+
+	let plans = match depth
+	{
+		Some(depth) => solve(&input, |robot_input| limited_search(robot_input, depth)),
+		None => solve(&input, nearest_neighbour),
+	};
+
+	let mut moves = Vec::new();
+	let mut plant_count = 0;
+	let mut value = 0;
+	let mut distance_traveled = 0;
+
+	for (robot, actions) in input.robots().iter().zip(&plans)
+	{
+		let (robot_moves, robot_plant_count, robot_distance_traveled) = resolve_robot(&input, robot, actions);
+
+		println!("Robot at {:?}: {robot_plant_count} plants, distance {robot_distance_traveled}", robot.start);
+
+		moves.push(Vec::from(robot_moves));
+		plant_count += robot_plant_count;
+		value += planted_value(&input, actions, robot_plant_count);
+		distance_traveled += robot_distance_traveled;
+	}
+
+	write_fleet_output(&moves, &plans, plant_count, value, distance_traveled);
+}
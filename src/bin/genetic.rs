@@ -0,0 +1,26 @@
+use codingup_qualifs::genetic::genetic;
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::{read_input, read_output_plans};
+use codingup_qualifs::solve_and_write_output;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn main() -> serde_json::Result<()>
+{
+	let population_size: usize = std::env::args().nth(2).unwrap().parse().unwrap();
+	let generations: usize = std::env::args().nth(3).unwrap().parse().unwrap();
+
+	let input = read_input()?;
+
+	// The solutions of the other binaries are recombined with the greedy ones
+	let mut initial = read_output_plans();
+	println!("{} plans read from the output", initial.len());
+	initial.push(nearest_neighbour(&input));
+	initial.push(limited_search(&input, 2));
+
+	let moves = genetic(&input, &initial, population_size, generations, &mut StdRng::seed_from_u64(0));
+
+	solve_and_write_output(&input, &moves);
+
+	Ok(())
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use codingup_qualifs::grasp::{grasp, Config};
+use codingup_qualifs::io::{read_input, Input};
+use codingup_qualifs::lin_kernighan::lin_kernighan;
+use codingup_qualifs::{solve_and_write_output, Action};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn main() -> serde_json::Result<()>
+{
+	let alpha: f64 = std::env::args().nth(2).unwrap().parse().unwrap();
+	let time_limit: f32 = std::env::args().nth(3).unwrap().parse().unwrap();
+	let seed: u64 = std::env::args().nth(4).map_or(0, |arg| arg.parse().unwrap());
+
+	let input = read_input()?;
+
+	let mut improver = |input: &Input, actions: &mut [Action]|
+	{
+		let improved = lin_kernighan(input, actions, 3);
+		actions.copy_from_slice(&improved);
+	};
+
+	let config = Config { alpha, time_limit: Duration::from_secs_f32(time_limit), max_iterations: usize::MAX };
+	let (moves, statistics) = grasp(&input, &mut improver, config, &mut StdRng::seed_from_u64(seed));
+
+	println!("{statistics:#?}");
+
+	solve_and_write_output(&input, &moves);
+
+	Ok(())
+}
@@ -18,7 +18,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		seed_storage: input.seed_capacity,
 		remaining_distance: input.max_distance,
 		seeds: input.seeds,
@@ -0,0 +1,29 @@
+use codingup_qualifs::io::{read_input, read_output, Input};
+use codingup_qualifs::iterated::{iterated_local_search, Acceptance, Config, Kick};
+use codingup_qualifs::lin_kernighan::lin_kernighan;
+use codingup_qualifs::{resolve_q_fast, solve_and_write_output, Action};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn main()
+{
+	let input = read_input().unwrap();
+	let actions = read_output();
+
+	let iterations = std::env::args().nth(3).unwrap().parse().unwrap();
+
+	println!("Base value {:?}", resolve_q_fast(&input, &actions, true));
+
+	let mut improver = |input: &Input, actions: &mut [Action]|
+	{
+		let improved = lin_kernighan(input, actions, 3);
+		actions.copy_from_slice(&improved);
+	};
+
+	let config = Config { iterations, kick: Kick::DoubleBridge, acceptance: Acceptance::Worse(10), checkpoint_every: 20 };
+
+	// The best plan is written every few iterations, a long run stopped early keeps it
+	let actions = iterated_local_search(&input, &actions, &mut improver, config, &mut StdRng::seed_from_u64(0), |best| solve_and_write_output(&input, best));
+
+	solve_and_write_output(&input, &actions);
+}
@@ -0,0 +1,16 @@
+use codingup_qualifs::io::{read_input, read_output};
+use codingup_qualifs::lin_kernighan::lin_kernighan;
+use codingup_qualifs::{resolve_q_fast, solve_and_write_output};
+
+fn main()
+{
+	let input = read_input().unwrap();
+	let actions = read_output();
+
+	let max_depth = std::env::args().nth(3).unwrap().parse().unwrap();
+
+	println!("Base value {:?}", resolve_q_fast(&input, &actions, true));
+	let actions = lin_kernighan(&input, &actions, max_depth);
+
+	solve_and_write_output(&input, &actions);
+}
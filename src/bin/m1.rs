@@ -50,7 +50,7 @@ fn main() -> serde_json::Result<()>
 	{
 		state: State
 		{
-			robot_pos: [0, 0],
+			robot_pos: input.start,
 			seed_storage: input.seed_capacity,
 			seeds: input.seeds.into(),
 			plants: input.plants.into(),
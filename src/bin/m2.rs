@@ -44,7 +44,7 @@ fn main() -> serde_json::Result<()>
 
 	let initial_state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		seed_storage: input.seed_capacity,
 		seeds: all_seeds,
 		plants: all_plants,
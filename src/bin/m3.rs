@@ -1,3 +1,5 @@
+#![allow(clippy::manual_is_multiple_of)]
+
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::rc::Rc;
 
@@ -42,7 +44,7 @@ fn main() -> serde_json::Result<()>
 
 	let initial_state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		seed_storage: input.seed_capacity,
 		seeds: all_seeds,
 		plants: all_plants,
@@ -0,0 +1,27 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::time::Duration;
+
+use codingup_qualifs::io::read_input;
+use codingup_qualifs::online::{rolling_horizon, Scenario};
+use codingup_qualifs::solve_and_write_output;
+
+fn main() -> serde_json::Result<()>
+{
+	let time_per_action: f32 = std::env::args().nth(2).unwrap().parse().unwrap();
+	let input = read_input()?;
+
+	// Without a scenario every plant is known upfront, the policy still plans one action at a time
+	let scenario: Scenario = match std::env::args().nth(3)
+	{
+		Some(scenario_path) => serde_json::from_reader(BufReader::new(File::open(scenario_path).unwrap()))?,
+		None => Scenario::default(),
+	};
+
+	let actions = rolling_horizon(&input, &scenario, Duration::from_secs_f32(time_per_action));
+
+	// The plan is scored against every plant of the scenario
+	solve_and_write_output(&scenario.full_input(&input), &actions);
+
+	Ok(())
+}
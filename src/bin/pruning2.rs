@@ -1,3 +1,5 @@
+#![allow(clippy::manual_is_multiple_of)]
+
 use codingup_qualifs::{distance, io::*, solve_and_write_output, Action, ActionKind};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -170,7 +172,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		seed_storage: input.seed_capacity,
 		seeds: input.seeds.clone(),
 		plants: input.plants.clone(),
@@ -257,7 +257,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		seed_storage: input.seed_capacity,
 		seeds: input.seeds.clone(),
 		plants: input.plants.clone(),
@@ -1,3 +1,5 @@
+#![allow(clippy::manual_is_multiple_of)]
+
 use std::cmp::Ordering;
 
 use codingup_qualifs::{distance, io::*};
@@ -262,7 +264,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		seed_storage: input.seed_capacity,
 		seeds: input.seeds.clone(),
 		plants: input.plants.clone(),
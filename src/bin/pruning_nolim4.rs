@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use codingup_qualifs::metric::PositionSet;
 use codingup_qualifs::quantum::QPos;
 use codingup_qualifs::{io::*, solve_and_write_output, Action, ActionKind};
 
@@ -216,7 +217,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: QPos::default(),
+		robot_pos: QPos::from_cell(input.start),
 		seed_storage: input.seed_capacity,
 		seeds: input.seeds.clone(),
 		plants: input.plants.clone(),
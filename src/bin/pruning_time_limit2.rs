@@ -1,3 +1,5 @@
+#![allow(clippy::legacy_numeric_constants, clippy::manual_is_multiple_of, clippy::too_many_arguments)]
+
 use std::cmp::Ordering;
 use std::i32;
 use std::time::{Duration, Instant};
@@ -311,7 +313,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		seed_storage: input.seed_capacity,
 		seeds: input.seeds.clone(),
 		plants: input.plants.clone(),
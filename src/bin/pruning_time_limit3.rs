@@ -1,6 +1,9 @@
+#![allow(clippy::manual_is_multiple_of, clippy::too_many_arguments)]
+
 use std::cmp::Ordering;
 use std::time::{Duration, Instant};
 
+use codingup_qualifs::metric::PositionSet;
 use codingup_qualifs::quantum::QPos;
 use codingup_qualifs::{io::*, solve_and_write_output, Action, ActionKind};
 use hashbrown::{Equivalent, HashMap};
@@ -254,7 +257,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: QPos::default(),
+		robot_pos: QPos::from_cell(input.start),
 		seed_storage: input.seed_capacity,
 		seeds: input.seeds.clone(),
 		plants: input.plants.clone(),
@@ -1,7 +1,10 @@
+#![allow(clippy::manual_is_multiple_of, clippy::too_many_arguments)]
+
 use std::cmp::Ordering;
 use std::time::{Duration, Instant};
 
 use codingup_qualifs::prim::prim2;
+use codingup_qualifs::metric::PositionSet;
 use codingup_qualifs::quantum::QPos;
 use codingup_qualifs::{io::*, solve_and_write_output, Action, ActionKind};
 use hashbrown::{Equivalent, HashMap};
@@ -329,7 +332,7 @@ fn main() -> serde_json::Result<()>
 
 	let mut state = State
 	{
-		robot_pos: QPos::default(),
+		robot_pos: QPos::from_cell(input.start),
 		seed_storage: input.seed_capacity,
 		seeds: input.seeds.clone(),
 		plants: input.plants.clone(),
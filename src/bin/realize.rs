@@ -0,0 +1,25 @@
+use codingup_qualifs::io::{read_input, read_output, write_output};
+use codingup_qualifs::realize::{realize, TieBreak};
+
+
+fn main()
+{
+	let input = read_input().unwrap();
+	let actions = read_output();
+
+	let tie_break = match std::env::args().nth(3).as_deref()
+	{
+		Some("seed") => TieBreak::NextSeed,
+		_ => TieBreak::Lexicographic,
+	};
+
+	let realization = realize(&input, &actions);
+
+	let slack = realization.slack();
+	let free_actions = slack.iter().filter(|&&count| count > 1).count();
+	println!("{free_actions}/{} actions have several optimal positions, {} at most", slack.len(), slack.iter().max().unwrap_or(&0));
+
+	let moves = realization.pick(&input, tie_break);
+
+	write_output(&moves, Some(&realization.actions), realization.plant_count, realization.distance_traveled);
+}
@@ -0,0 +1,36 @@
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::read_input;
+use codingup_qualifs::robust::{evaluate, robust_plan};
+use codingup_qualifs::solve_and_write_output;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn main() -> serde_json::Result<()>
+{
+	let samples: usize = std::env::args().nth(2).unwrap().parse().unwrap();
+
+	let input = read_input()?;
+
+	let candidates = [nearest_neighbour(&input), limited_search(&input, 2), limited_search(&input, 3)];
+
+	// The plan planting the most on average, then the one overrunning the least
+	let Some((plan, evaluation)) = candidates.iter()
+		.filter_map(|actions|
+		{
+			let plan = robust_plan(&input, actions);
+			let evaluation = evaluate(&input, &plan, samples, &mut StdRng::seed_from_u64(0))?;
+			Some((plan, evaluation))
+		})
+		.max_by(|(_, a), (_, b)| a.expected_value.total_cmp(&b.expected_value).then(b.overrun_rate.total_cmp(&a.overrun_rate)))
+	else
+	{
+		eprintln!("The failures are not replayed with species or pile sizes");
+		return Ok(());
+	};
+
+	println!("{evaluation:?}, {} fallbacks", plan.fallbacks.len());
+
+	solve_and_write_output(&input, &plan.actions);
+
+	Ok(())
+}
@@ -0,0 +1,14 @@
+use codingup_qualifs::io::read_input;
+use codingup_qualifs::savings::savings;
+use codingup_qualifs::solve_and_write_output;
+
+fn main() -> serde_json::Result<()>
+{
+	let input = read_input()?;
+
+	let moves = savings(&input);
+
+	solve_and_write_output(&input, &moves);
+
+	Ok(())
+}
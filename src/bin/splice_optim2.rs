@@ -1,31 +1,7 @@
 use codingup_qualifs::io::{read_input, read_output, Input};
-use codingup_qualifs::{resolve_fast, solve_and_write_output, Action, ActionKind};
+use codingup_qualifs::{check_actions_at, resolve_fast, solve_and_write_output, Action, ActionKind};
 
 
-fn check_actions_at(input: &Input, actions: &[Action], index: usize) -> bool
-{
-	let mut seed = input.seed_capacity as i32;
-	for action in &actions[index..]
-	{
-		if action.kind == ActionKind::Collect
-		{
-			break;
-		}
-		seed -= 1;
-	}
-
-	for action in actions[0..index].iter().rev()
-	{
-		if action.kind == ActionKind::Collect
-		{
-			break;
-		}
-		seed -= 1;
-	}
-
-	seed >= 0
-}
-
 fn check_actions(input: &Input, actions: &[Action], slice_start: usize, slice_end: usize, d: usize) -> bool
 {
 	   check_actions_at(input, actions, slice_start)
@@ -0,0 +1,17 @@
+use codingup_qualifs::io::{read_input, read_output};
+use codingup_qualifs::tabu::tabu_search;
+use codingup_qualifs::{resolve_q_fast, solve_and_write_output};
+
+fn main()
+{
+	let input = read_input().unwrap();
+	let actions = read_output();
+
+	let iterations = std::env::args().nth(3).unwrap().parse().unwrap();
+	let tenure = std::env::args().nth(4).unwrap().parse().unwrap();
+
+	println!("Base value {:?}", resolve_q_fast(&input, &actions, true));
+	let actions = tabu_search(&input, &actions, iterations, tenure);
+
+	solve_and_write_output(&input, &actions);
+}
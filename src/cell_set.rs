@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+
+use crate::io::Input;
+use crate::metric::PositionSet;
+use crate::obstacles;
+use crate::quantum::{QPos, QRect};
+use crate::{Action, ActionKind};
+
+
+// Explicit set of equally good robot positions, slow reference for `QPos` and fallback for the other metrics
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CellSet
+{
+	pub cells: BTreeSet<[i32; 2]>,
+}
+
+
+impl CellSet
+{
+	pub fn apply_plant(&self, input: &Input, plant_pos: [i32; 2]) -> (Self, i32)
+	{
+		let metric = input.metric;
+
+		let in_range: BTreeSet<[i32; 2]> = self.cells.iter()
+			.copied()
+			.filter(|&cell| metric.distance(cell, plant_pos) <= input.range)
+			.collect();
+
+		if !in_range.is_empty()
+		{
+			// No move required, keep the positions already in range
+			return (CellSet { cells: in_range }, 0);
+		}
+
+		// Every cell at the edge of the plant range reachable with the minimum distance, around obstacles
+		// the nearest free cells may be anywhere in the range
+		let cells: Vec<[i32; 2]> = if input.obstacles.is_empty()
+		{
+			metric.edge(plant_pos, input.range).collect()
+		}
+		else
+		{
+			metric.ball(plant_pos, input.range).filter(|&pos| !obstacles::is_blocked(input, pos)).collect()
+		};
+
+		let edge: Vec<([i32; 2], i32)> = cells.into_iter()
+			.map(|pos| (pos, self.cells.iter().map(|&cell| input.travel_distance(cell, pos)).min().unwrap()))
+			.collect();
+
+		let dist = edge.iter().map(|&(_, dist)| dist).min().unwrap_or(obstacles::UNREACHABLE);
+
+		let cells = edge.into_iter()
+			.filter(|&(_, pos_dist)| pos_dist == dist)
+			.map(|(pos, _)| pos)
+			.collect();
+
+		(CellSet { cells }, dist)
+	}
+
+	pub fn apply_seed(&self, input: &Input, seed_pos: [i32; 2]) -> (Self, i32)
+	{
+		let dist = self.cells.iter()
+			.map(|&cell| input.travel_distance(cell, seed_pos))
+			.min()
+			.unwrap();
+
+		(CellSet { cells: [seed_pos].into() }, dist)
+	}
+
+	pub fn apply_action(&self, input: &Input, action: &Action) -> (Self, i32)
+	{
+		match action.kind
+		{
+			ActionKind::Plant => self.apply_plant(input, action.pos),
+			ActionKind::Collect => self.apply_seed(input, action.pos),
+		}
+	}
+}
+
+impl PositionSet for CellSet
+{
+	fn from_cell(pos: [i32; 2]) -> Self
+	{
+		CellSet { cells: [pos].into() }
+	}
+
+	fn apply_plant(&self, input: &Input, plant_pos: [i32; 2]) -> (Self, i32)
+	{
+		CellSet::apply_plant(self, input, plant_pos)
+	}
+
+	fn apply_seed(&self, input: &Input, seed_pos: [i32; 2]) -> (Self, i32)
+	{
+		CellSet::apply_seed(self, input, seed_pos)
+	}
+}
+
+
+impl From<QPos> for CellSet
+{
+	fn from(pos: QPos) -> Self
+	{
+		let sign = if pos.range < 0 { -1 } else { 1 };
+
+		CellSet
+		{
+			cells: (0..=pos.range.abs())
+				.map(|t| [pos.robot_pos[0] + sign * t, pos.robot_pos[1] + t])
+				.collect(),
+		}
+	}
+}
+
+impl From<QRect> for CellSet
+{
+	fn from(pos: QRect) -> Self
+	{
+		CellSet
+		{
+			cells: pos.cells().collect(),
+		}
+	}
+}
@@ -0,0 +1,304 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::cell_set::CellSet;
+use crate::greedy::{cost_per_value, State};
+use crate::io::Input;
+use crate::metric::{Metric, PositionSet};
+use crate::obstacles;
+use crate::quantum::QPos;
+use crate::{Action, ActionKind};
+
+
+// A cell the robot stops at and the plants it plants from there, at most one seed storage of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stop
+{
+	pub pos: [i32; 2],
+	pub plants: Vec<[i32; 2]>,
+}
+
+
+// In the rotated frame [x+y, x-y], the cells within the range of a plant form a square
+fn rotate(pos: [i32; 2]) -> [i32; 2]
+{
+	[pos[0] + pos[1], pos[0] - pos[1]]
+}
+
+fn unrotate(pos: [i32; 2]) -> [i32; 2]
+{
+	[(pos[0] + pos[1]) / 2, (pos[0] - pos[1]) / 2]
+}
+
+// The Manhattan stops are searched in the rotated frame, the other metrics in the frame of the field
+fn to_frame(input: &Input, pos: [i32; 2]) -> [i32; 2]
+{
+	if input.metric == Metric::Manhattan { rotate(pos) } else { pos }
+}
+
+fn from_frame(input: &Input, pos: [i32; 2]) -> [i32; 2]
+{
+	if input.metric == Metric::Manhattan { unrotate(pos) } else { pos }
+}
+
+fn in_range(input: &Input, a: [i32; 2], b: [i32; 2]) -> bool
+{
+	if input.metric == Metric::Manhattan
+	{
+		(a[0] - b[0]).abs() <= input.range && (a[1] - b[1]).abs() <= input.range
+	}
+	else
+	{
+		input.metric.distance(a, b) <= input.range
+	}
+}
+
+
+// Plants in buckets of the rotated frame, one bucket per square of a plant range
+struct Buckets
+{
+	size: i32,
+	buckets: HashMap<[i32; 2], Vec<usize>>,
+}
+
+impl Buckets
+{
+	fn new(points: &[[i32; 2]], size: i32) -> Self
+	{
+		let mut buckets: HashMap<[i32; 2], Vec<usize>> = HashMap::new();
+
+		for (index, &point) in points.iter().enumerate()
+		{
+			buckets.entry([point[0].div_euclid(size), point[1].div_euclid(size)]).or_default().push(index);
+		}
+
+		Buckets { size, buckets }
+	}
+
+	// Points in the buckets touching the square of half side `size` around the point
+	fn around(&self, point: [i32; 2]) -> impl Iterator<Item = usize> + '_
+	{
+		let bucket = [point[0].div_euclid(self.size), point[1].div_euclid(self.size)];
+
+		(-1..=1)
+			.flat_map(move |du| (-1..=1).map(move |dv| [bucket[0] + du, bucket[1] + dv]))
+			.filter_map(|bucket| self.buckets.get(&bucket))
+			.flatten()
+			.copied()
+	}
+}
+
+
+// Stops whose range square has its lowest corners at the lowest coordinates of two plants. Every group of plants
+// a cell can reach is reached from one of them: the square can always be pushed to the lowest coordinates of the
+// group, and when that cell has the wrong parity one of its two neighbours inside the square reaches the group.
+fn candidates(plants: &[[i32; 2]], range: i32, buckets: &Buckets) -> Vec<[i32; 2]>
+{
+	let mut candidates: Vec<[i32; 2]> = plants.iter()
+		.flat_map(|&p| buckets.around(p)
+			.map(|index| plants[index])
+			.filter(move |q| (p[0]..=p[0] + 2 * range).contains(&q[0]) && (p[1] - 2 * range..=p[1]).contains(&q[1]))
+			.flat_map(move |q|
+			{
+				let (u, v) = (p[0] + range, q[1] + range);
+
+				if (u - v).rem_euclid(2) == 0 { vec![[u, v]] } else { vec![[u, v - 1], [u - 1, v]] }
+			}))
+		.collect();
+
+	candidates.sort_unstable();
+	candidates.dedup();
+	candidates
+}
+
+// Every free cell in range of a plant, for the other metrics and around obstacles
+fn range_cells(input: &Input) -> Vec<[i32; 2]>
+{
+	let mut candidates: Vec<[i32; 2]> = input.plants.iter()
+		.flat_map(|&plant| input.metric.ball(plant, input.range.max(0)))
+		.filter(|&cell| !obstacles::is_blocked(input, cell))
+		.map(|cell| to_frame(input, cell))
+		.collect();
+
+	candidates.sort_unstable();
+	candidates.dedup();
+	candidates
+}
+
+
+// Greedy set cover of the plants by stops: each time the candidate cell reaching the most plants not planted yet,
+// at most `seed_capacity` of them. Every plant with a free cell in range ends up in exactly one stop.
+pub fn stops(input: &Input) -> Vec<Stop>
+{
+	let range = input.range.max(0);
+	let capacity = input.seed_capacity.max(1) as usize;
+
+	let plants: Vec<[i32; 2]> = input.plants.iter().map(|&plant| to_frame(input, plant)).collect();
+	let buckets = Buckets::new(&plants, 2 * range + 1);
+
+	let candidates = if input.exact_qpos()
+	{
+		candidates(&plants, range, &buckets)
+	}
+	else
+	{
+		range_cells(input)
+	};
+
+	let reached: Vec<Vec<usize>> = candidates.iter()
+		.map(|&cell|
+		{
+			let mut reached: Vec<usize> = buckets.around(cell).filter(|&index| in_range(input, cell, plants[index])).collect();
+			reached.sort_unstable();
+			reached
+		})
+		.collect();
+
+	let mut planted = vec![false; plants.len()];
+	let mut stops = Vec::new();
+
+	// Plants reached by each candidate only go down, a candidate at the top of the heap with an exact count is the best
+	let mut heap: BinaryHeap<(usize, Reverse<usize>)> = reached.iter()
+		.enumerate()
+		.map(|(index, reached)| (reached.len().min(capacity), Reverse(index)))
+		.collect();
+
+	while let Some((count, Reverse(index))) = heap.pop()
+	{
+		let left: Vec<usize> = reached[index].iter().copied().filter(|&plant| !planted[plant]).collect();
+		let new_count = left.len().min(capacity);
+
+		if new_count < count
+		{
+			if new_count > 0
+			{
+				heap.push((new_count, Reverse(index)));
+			}
+			continue;
+		}
+
+		for &plant in &left[..new_count]
+		{
+			planted[plant] = true;
+		}
+
+		stops.push(Stop
+		{
+			pos: from_frame(input, candidates[index]),
+			plants: left[..new_count].iter().map(|&plant| input.plants[plant]).collect(),
+		});
+
+		if left.len() > new_count
+		{
+			heap.push(((left.len() - new_count).min(capacity), Reverse(index)));
+		}
+	}
+
+	stops
+}
+
+// Plan planting the plants of the stops, in the order of the stops
+pub fn expand(stops: &[Stop]) -> Vec<Action>
+{
+	stops.iter()
+		.flat_map(|stop| &stop.plants)
+		.map(|&pos| Action { pos, kind: ActionKind::Plant })
+		.collect()
+}
+
+
+// Nearest neighbour between stops: the robot goes to the stop with the lowest distance per unit of value among the
+// ones its seeds can plant, otherwise it collects the nearest seed. The distance of a stop is the one to plant
+// all its plants, so the robot stays at the stop when it can. The seeds follow the species and the pile sizes
+// like the greedy.
+pub fn route_stops(input: &Input, stops: &[Stop]) -> Vec<Action>
+{
+	if input.exact_qpos()
+	{
+		route::<QPos>(input, stops)
+	}
+	else
+	{
+		route::<CellSet>(input, stops)
+	}
+}
+
+fn route<P: PositionSet>(input: &Input, stops: &[Stop]) -> Vec<Action>
+{
+	let mut pos = P::from_cell(input.start);
+	let mut state = State::new(input);
+	let mut distance_traveled = 0;
+
+	let mut stops: Vec<&Stop> = stops.iter().collect();
+
+	let mut actions = Vec::new();
+
+	while !stops.is_empty()
+	{
+		let visit = |stop: &Stop| stop.plants.iter().fold((pos.clone(), 0), |(pos, dist), &plant|
+		{
+			let (new_pos, step) = pos.apply_plant(input, plant);
+			(new_pos, dist + step)
+		});
+
+		let fits = |stop: &Stop|
+		{
+			let mut inventory = state.inventory.clone();
+			stop.plants.iter().all(|&plant| inventory.plant(input, plant))
+		};
+
+		let next = stops.iter()
+			.enumerate()
+			.filter(|(_, stop)| fits(stop))
+			.map(|(index, stop)|
+			{
+				let (new_pos, dist) = visit(stop);
+				let value = stop.plants.iter().map(|&plant| input.plant_value(plant)).sum::<u32>();
+				(index, new_pos, dist, value)
+			})
+			.filter(|(_, new_pos, dist, _)| !input.return_to_base || distance_traveled + dist + new_pos.return_distance(input) <= input.max_distance as i32)
+			.min_by(|&(_, _, a, a_value), &(_, _, b, b_value)| cost_per_value((a, a_value), (b, b_value)));
+
+		if let Some((index, new_pos, dist, _)) = next
+		{
+			let stop = stops.remove(index);
+
+			for &plant in &stop.plants
+			{
+				state.inventory.plant(input, plant);
+				state.plants.retain(|&other| other != plant);
+			}
+
+			actions.extend(stop.plants.iter().map(|&pos| Action { pos, kind: ActionKind::Plant }));
+			pos = new_pos;
+			distance_traveled += dist;
+		}
+		else
+		{
+			// Collecting only helps for the stops the seeds carried cannot plant
+			if stops.iter().all(|stop| fits(stop)) || state.inventory.is_full(input)
+			{
+				break;
+			}
+
+			// With species, a seed pile is chosen with the way to the next plant it can be used for
+			let Some((_, index)) = (0..state.seeds.len())
+				.filter_map(|index| Some((pos.apply_seed(input, state.seeds[index]).1 + state.seed_detour(input, state.seeds[index])?, index)))
+				.min()
+			else
+			{
+				break;
+			};
+
+			let seed_pos = state.seeds[index];
+			let (new_pos, dist) = pos.apply_seed(input, seed_pos);
+
+			actions.push(Action { pos: seed_pos, kind: ActionKind::Collect });
+			state.collect(input, index);
+			pos = new_pos;
+			distance_traveled += dist;
+		}
+	}
+
+	actions
+}
@@ -0,0 +1,260 @@
+use crate::cell_set::CellSet;
+use crate::fleet::{k_means, nearest, robot_input, Assignment};
+use crate::io::{Input, Robot};
+use crate::metric::PositionSet;
+use crate::obstacles::Distances;
+use crate::quantum::QPos;
+use crate::{Action, ActionKind};
+
+
+// Plants and seeds of a cluster, around its center
+#[derive(Debug, Clone)]
+pub struct Cluster
+{
+	pub center: [i32; 2],
+	pub plants: Vec<[i32; 2]>,
+	pub seeds: Vec<[i32; 2]>,
+}
+
+
+// Seed piles spread over the field: the pile nearest to the start, then each time the pile farthest from the ones taken
+fn spread_piles(input: &Input, distances: &Distances, count: usize) -> Vec<[i32; 2]>
+{
+	let Some(&first) = input.seeds.iter().min_by_key(|&&seed| distances.distance(seed, input.start))
+	else
+	{
+		return Vec::new();
+	};
+
+	let mut piles = vec![first];
+	// Distance from each seed to the nearest pile taken
+	let mut gaps: Vec<i32> = input.seeds.iter().map(|&seed| distances.distance(seed, first)).collect();
+
+	while piles.len() < count.min(input.seeds.len())
+	{
+		let farthest = input.seeds[(0..gaps.len()).max_by_key(|&index| gaps[index]).unwrap()];
+
+		for (gap, &seed) in gaps.iter_mut().zip(&input.seeds)
+		{
+			*gap = (*gap).min(distances.distance(seed, farthest));
+		}
+
+		piles.push(farthest);
+	}
+
+	piles
+}
+
+// k-means on the plants, starting from `count` spread seed piles. Seeds go to the nearest cluster,
+// clusters without plants are dropped.
+pub fn clusters(input: &Input, count: usize) -> Vec<Cluster>
+{
+	let distances = Distances::new(input);
+
+	let mut starts = spread_piles(input, &distances, count);
+	if starts.is_empty()
+	{
+		starts.push(input.start);
+	}
+
+	let (centers, plant_clusters) = k_means(&distances, &input.plants, starts);
+
+	let mut clusters: Vec<Cluster> = centers.iter()
+		.map(|&center| Cluster { center, plants: Vec::new(), seeds: Vec::new() })
+		.collect();
+
+	for (&plant, &cluster) in input.plants.iter().zip(&plant_clusters)
+	{
+		clusters[cluster].plants.push(plant);
+	}
+
+	clusters.retain(|cluster| !cluster.plants.is_empty());
+
+	if clusters.is_empty()
+	{
+		return clusters;
+	}
+
+	let centers: Vec<[i32; 2]> = clusters.iter().map(|cluster| cluster.center).collect();
+	for &seed in &input.seeds
+	{
+		clusters[nearest(&distances, &centers, seed)].seeds.push(seed);
+	}
+
+	clusters
+}
+
+
+fn path_length(distances: &Distances, start: [i32; 2], clusters: &[Cluster], order: &[usize], closed: bool) -> i32
+{
+	let mut length = 0;
+	let mut pos = start;
+
+	for &index in order
+	{
+		length += distances.distance(pos, clusters[index].center);
+		pos = clusters[index].center;
+	}
+
+	if closed
+	{
+		length += distances.distance(pos, start);
+	}
+
+	length
+}
+
+// Visiting order of the clusters: nearest center first from the start, then 2-opt on the path between centers.
+// The path comes back to the start when the robot must return.
+pub fn cluster_order(input: &Input, clusters: &[Cluster]) -> Vec<usize>
+{
+	let distances = Distances::new(input);
+
+	let mut order = Vec::new();
+	let mut left: Vec<usize> = (0..clusters.len()).collect();
+	let mut pos = input.start;
+
+	while !left.is_empty()
+	{
+		let index = (0..left.len()).min_by_key(|&index| distances.distance(pos, clusters[left[index]].center)).unwrap();
+		pos = clusters[left[index]].center;
+		order.push(left.swap_remove(index));
+	}
+
+	let mut length = path_length(&distances, input.start, clusters, &order, input.return_to_base);
+
+	let mut improved = true;
+	while improved
+	{
+		improved = false;
+
+		for i in 0..order.len()
+		{
+			for j in i + 1..order.len()
+			{
+				order[i..=j].reverse();
+
+				let new_length = path_length(&distances, input.start, clusters, &order, input.return_to_base);
+
+				if new_length < length
+				{
+					length = new_length;
+					improved = true;
+				}
+				else
+				{
+					order[i..=j].reverse();
+				}
+			}
+		}
+	}
+
+	order
+}
+
+
+// Cluster first, route second: the plants are clustered around seed piles, the clusters are visited in the order of
+// a path over their centers, and each one is solved by `inner` from the cell of the robot position nearest to its
+// center, with the remaining distance but the way through the next centers. A cluster entered without a full
+// seed storage starts by collecting its pile nearest to the robot, or plants with the seeds left when it has none.
+// Returns the plan joining the plans of the clusters, `None` with species, pile sizes or time windows: the seeds
+// carried and the time do not follow from one cluster to the next.
+// A cluster only collects its own piles and ignores the plants of the next ones, the plan plants less than `inner`
+// on the whole field, most on the instances with few seeds to spare.
+pub fn decompose(input: &Input, cluster_count: usize, inner: impl Fn(&Input) -> Vec<Action>) -> Option<Vec<Action>>
+{
+	if input.has_species() || input.has_piles() || input.has_windows()
+	{
+		return None;
+	}
+
+	let actions = if input.exact_qpos()
+	{
+		join::<QPos>(input, cluster_count, inner)
+	}
+	else
+	{
+		join::<CellSet>(input, cluster_count, inner)
+	};
+
+	Some(actions)
+}
+
+fn join<P: PositionSet + Into<CellSet>>(input: &Input, cluster_count: usize, inner: impl Fn(&Input) -> Vec<Action>) -> Vec<Action>
+{
+	let distances = Distances::new(input);
+
+	let clusters = clusters(input, cluster_count);
+	let order = cluster_order(input, &clusters);
+
+	// Way from the center of each cluster of the order through the centers of the next ones, and back
+	let mut onward = vec![0; order.len()];
+	for position in (0..order.len()).rev()
+	{
+		let center = clusters[order[position]].center;
+
+		onward[position] = match order.get(position + 1)
+		{
+			Some(&next) => distances.distance(center, clusters[next].center) + onward[position + 1],
+			None => distances.return_distance(center),
+		};
+	}
+
+	let mut pos = P::from_cell(input.start);
+	let mut seed_storage = input.seed_capacity;
+	let mut distance_left = input.max_distance as i32;
+
+	let mut actions = Vec::new();
+
+	for (position, index) in order.into_iter().enumerate()
+	{
+		let cluster = &clusters[index];
+
+		let mut start = pos.clone().into().cells.into_iter().min_by_key(|&cell| distances.distance(cell, cluster.center)).unwrap();
+		let mut seeds = cluster.seeds.clone();
+		let mut seed_capacity = input.seed_capacity;
+
+		let mut cluster_actions = Vec::new();
+
+		if seed_storage < input.seed_capacity
+		{
+			match (0..seeds.len()).min_by_key(|&seed_index| distances.distance(seeds[seed_index], start))
+			{
+				Some(seed_index) =>
+				{
+					start = seeds.swap_remove(seed_index);
+					cluster_actions.push(Action { pos: start, kind: ActionKind::Collect });
+				},
+				None => seed_capacity = seed_storage,
+			}
+		}
+
+		let entry = cluster_actions.first().map_or(0, |action| pos.apply_action(input, action).1);
+
+		// The distance a cluster does not use goes to the next ones, only the way through the next centers is kept
+		let share = (distance_left - entry - onward[position]).max(0);
+
+		let robot = Robot { start, max_distance: share as u32, seed_capacity };
+		let mut cluster_input = robot_input(input, &robot, &Assignment { plants: cluster.plants.clone(), seeds });
+		cluster_input.return_to_base = false;
+
+		cluster_actions.extend(inner(&cluster_input));
+
+		for action in cluster_actions
+		{
+			let (new_pos, dist) = pos.apply_action(input, &action);
+			pos = new_pos;
+			distance_left -= dist;
+
+			match action.kind
+			{
+				ActionKind::Plant => seed_storage -= 1,
+				ActionKind::Collect => seed_storage = input.seed_capacity,
+			}
+
+			actions.push(action);
+		}
+	}
+
+	actions
+}
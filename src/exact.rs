@@ -0,0 +1,200 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::io::{Input, OutAction};
+use crate::metric::Metric;
+use crate::{distance, planted_value, resolve, Action, ActionKind};
+
+
+// Every robot position reachable after a prefix of actions, with the minimum distance to reach it.
+// This is exactly one layer of the graph explored by `resolve`.
+type Frontier = Vec<([i32; 2], i32)>;
+
+
+fn apply_plant(input: &Input, frontier: &Frontier, plant_pos: [i32; 2]) -> Frontier
+{
+	let mut cells: HashMap<[i32; 2], i32> = HashMap::new();
+
+	let mut push = |pos: [i32; 2], distance_traveled: i32|
+	{
+		let best = cells.entry(pos).or_insert(distance_traveled);
+		*best = i32::min(*best, distance_traveled);
+	};
+
+	for &(pos, distance_traveled) in frontier
+	{
+		let delta = [plant_pos[0] - pos[0], plant_pos[1] - pos[1]];
+		let abs = [delta[0].abs(), delta[1].abs()];
+		let dist = abs[0] + abs[1];
+
+		if distance_traveled + (dist - input.range).max(0) > input.max_distance as i32
+		{
+			continue;
+		}
+
+		if dist <= input.range
+		{
+			// No move required
+			push(pos, distance_traveled);
+		}
+		else
+		{
+			// Move to any cell of the diamond edge facing the robot
+			let new_distance_traveled = distance_traveled + dist - input.range;
+
+			let sign = [delta[0].signum(), delta[1].signum()];
+			for dx in i32::max(0, input.range - abs[1])..=i32::min(abs[0], input.range)
+			{
+				let dy = input.range - dx;
+				push([plant_pos[0] - sign[0] * dx, plant_pos[1] - sign[1] * dy], new_distance_traveled);
+			}
+		}
+	}
+
+	let mut frontier: Frontier = cells.into_iter()
+		.filter(|&(pos, distance_traveled)| distance_traveled + input.return_distance(pos) <= input.max_distance as i32)
+		.collect();
+	frontier.sort_unstable();
+	frontier
+}
+
+fn apply_seed(input: &Input, frontier: &Frontier, seed_pos: [i32; 2]) -> Frontier
+{
+	frontier.iter()
+		.map(|&(pos, distance_traveled)| distance_traveled + distance(pos, seed_pos))
+		.min()
+		.filter(|&distance_traveled| distance_traveled + input.return_distance(seed_pos) <= input.max_distance as i32)
+		.map(|distance_traveled| vec![(seed_pos, distance_traveled)])
+		.unwrap_or_default()
+}
+
+// Minimum distance of a plan ending in the frontier, with the return leg
+fn min_distance(input: &Input, frontier: &Frontier) -> i32
+{
+	frontier.iter().map(|&(pos, distance_traveled)| distance_traveled + input.return_distance(pos)).min().unwrap()
+}
+
+
+struct Search<'l>
+{
+	input: &'l Input,
+	actions: Vec<Action>,
+	// Planted value and distance of the best plan
+	best_value: (u32, i32),
+	best_actions: Vec<Action>,
+}
+
+impl Search<'_>
+{
+	fn explore(&mut self, frontier: &Frontier, plants_left: u32, seeds_left: u32, seed_storage: u32, value: u32)
+	{
+		let input = self.input;
+
+		let min_dist = min_distance(input, frontier);
+
+		// Upper bound of the value reachable from this node: the most valuable plants left, as many as there are seeds.
+		// Distance can only increase.
+		let max_plant_count = u32::min(plants_left.count_ones(), seed_storage + input.seed_capacity * seeds_left.count_ones());
+
+		let mut values_left: Vec<u32> = (0..input.plants.len())
+			.filter(|index| plants_left & (1 << index) != 0)
+			.map(|index| input.plant_value(input.plants[index]))
+			.collect();
+		values_left.sort_unstable_by(|a, b| b.cmp(a));
+
+		let max_value = value + values_left.iter().take(max_plant_count as usize).sum::<u32>();
+
+		if (max_value, -min_dist) <= (self.best_value.0, -self.best_value.1)
+		{
+			return;
+		}
+
+		if seed_storage > 0
+		{
+			let mut children: Vec<(i32, usize, Frontier)> = (0..input.plants.len())
+				.filter(|index| plants_left & (1 << index) != 0)
+				.filter_map(|index|
+				{
+					let new_frontier = apply_plant(input, frontier, input.plants[index]);
+					(!new_frontier.is_empty()).then(|| (min_distance(input, &new_frontier), index, new_frontier))
+				})
+				.collect();
+
+			// Explore the cheapest plants first to find good bounds early
+			children.sort_unstable_by_key(|(dist, index, _)| (*dist, *index));
+
+			for (dist, index, new_frontier) in children
+			{
+				self.actions.push(Action { pos: input.plants[index], kind: ActionKind::Plant });
+
+				let new_value = value + input.plant_value(input.plants[index]);
+
+				if (new_value, -dist) > (self.best_value.0, -self.best_value.1)
+				{
+					self.best_value = (new_value, dist);
+					self.best_actions = self.actions.clone();
+				}
+
+				self.explore(&new_frontier, plants_left & !(1 << index), seeds_left, seed_storage - 1, new_value);
+
+				self.actions.pop();
+			}
+		}
+
+		if seed_storage < input.seed_capacity && plants_left != 0
+		{
+			for index in 0..input.seeds.len()
+			{
+				if seeds_left & (1 << index) == 0
+				{
+					continue;
+				}
+
+				let new_frontier = apply_seed(input, frontier, input.seeds[index]);
+
+				if new_frontier.is_empty()
+				{
+					continue;
+				}
+
+				self.actions.push(Action { pos: input.seeds[index], kind: ActionKind::Collect });
+
+				self.explore(&new_frontier, plants_left, seeds_left & !(1 << index), input.seed_capacity, value);
+
+				self.actions.pop();
+			}
+		}
+	}
+}
+
+
+// Exhaustive search over every plant order and seed insertion, only usable on tiny instances.
+// Returns the optimal actions with their exact resolution by `resolve`.
+pub fn solve_exact(input: &Input) -> (Vec<Action>, VecDeque<OutAction>, usize, i32)
+{
+	assert!(input.plants.len() <= 32 && input.seeds.len() <= 32, "Instance too large for the exact solver");
+	assert_eq!(input.metric, Metric::Manhattan, "The exact solver only supports the Manhattan metric");
+	assert!(!input.has_species(), "The exact solver does not support seed species");
+	assert!(!input.has_piles(), "The exact solver does not support pile sizes");
+	assert!(!input.has_windows(), "The exact solver does not support time windows");
+	assert!(input.obstacles.is_empty(), "The exact solver does not support obstacles");
+	assert!(input.robots.is_empty(), "The exact solver only supports a single robot");
+
+	let mut search = Search
+	{
+		input,
+		actions: Vec::new(),
+		best_value: (0, 0),
+		best_actions: Vec::new(),
+	};
+
+	let all_plants = (1_u64 << input.plants.len()) - 1;
+	let all_seeds = (1_u64 << input.seeds.len()) - 1;
+
+	search.explore(&vec![(input.start, 0)], all_plants as u32, all_seeds as u32, input.seed_capacity, 0);
+
+	let (out_actions, plant_count, distance_traveled) = resolve(input, &search.best_actions);
+
+	assert_eq!((planted_value(input, &search.best_actions, plant_count), distance_traveled), search.best_value);
+
+	(search.best_actions, out_actions, plant_count, distance_traveled)
+}
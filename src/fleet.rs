@@ -0,0 +1,256 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::io::{Input, OutAction, Robot};
+use crate::obstacles::Distances;
+use crate::{planted_value, resolve, Action, ActionKind};
+
+
+const REBALANCE_PASSES: usize = 3;
+
+
+// Plants and seeds given to one robot
+#[derive(Debug, Clone, Default)]
+pub struct Assignment
+{
+	pub plants: Vec<[i32; 2]>,
+	pub seeds: Vec<[i32; 2]>,
+}
+
+
+fn requirements(input: &Input, plant: [i32; 2]) -> Vec<u32>
+{
+	input.plant_index(plant)
+		.and_then(|index| input.requirements.get(index))
+		.cloned()
+		.unwrap_or_default()
+}
+
+
+fn pile_size(input: &Input, seed: [i32; 2]) -> u32
+{
+	input.seed_index(seed).map_or(0, |index| input.pile_sizes[index])
+}
+
+
+// Single robot input with the plants and seeds assigned to the robot
+pub fn robot_input(input: &Input, robot: &Robot, assignment: &Assignment) -> Input
+{
+	let mut robot_input = Input
+	{
+		start: robot.start,
+		max_distance: robot.max_distance,
+		seed_capacity: robot.seed_capacity,
+		range: input.range,
+		seeds: assignment.seeds.clone(),
+		plants: assignment.plants.clone(),
+		values: if input.is_weighted() { assignment.plants.iter().map(|&plant| input.plant_value(plant)).collect() } else { Vec::new() },
+		species: if input.has_species() { assignment.seeds.iter().map(|&seed| input.seed_species(seed).unwrap()).collect() } else { Vec::new() },
+		requirements: assignment.plants.iter().map(|&plant| requirements(input, plant)).collect(),
+		windows: if input.has_windows() { assignment.plants.iter().map(|&plant| input.window(plant)).collect() } else { Vec::new() },
+		plant_duration: input.plant_duration,
+		collect_duration: input.collect_duration,
+		pile_sizes: if input.has_piles() { assignment.seeds.iter().map(|&seed| pile_size(input, seed)).collect() } else { Vec::new() },
+		obstacles: input.obstacles.clone(),
+		metric: input.metric,
+		return_to_base: input.return_to_base,
+		..Default::default()
+	};
+
+	robot_input.index();
+	robot_input
+}
+
+// `resolve` for the command stream of one robot
+pub fn resolve_robot(input: &Input, robot: &Robot, actions: &[Action]) -> (VecDeque<OutAction>, usize, i32)
+{
+	// Every plant and seed is kept for their values and species
+	resolve(&robot_input(input, robot, &Assignment { plants: input.plants.clone(), seeds: input.seeds.clone() }), actions)
+}
+
+
+pub fn nearest(distances: &Distances, centers: &[[i32; 2]], pos: [i32; 2]) -> usize
+{
+	(0..centers.len()).min_by_key(|&index| distances.distance(centers[index], pos)).unwrap()
+}
+
+// k-means from the given centers with the travel distance, returns the final centers and the cluster of each point
+pub fn k_means(distances: &Distances, points: &[[i32; 2]], mut centers: Vec<[i32; 2]>) -> (Vec<[i32; 2]>, Vec<usize>)
+{
+	let mut clusters: Vec<usize> = Vec::new();
+
+	for _ in 0..100
+	{
+		let new_clusters: Vec<usize> = points.iter().map(|&point| nearest(distances, &centers, point)).collect();
+
+		if new_clusters == clusters
+		{
+			break;
+		}
+
+		clusters = new_clusters;
+
+		for (index, center) in centers.iter_mut().enumerate()
+		{
+			let members: Vec<[i32; 2]> = (0..points.len())
+				.filter(|&point_index| clusters[point_index] == index)
+				.map(|point_index| points[point_index])
+				.collect();
+
+			if members.is_empty()
+			{
+				continue;
+			}
+
+			let count = members.len() as i64;
+			let sum = members.iter().fold([0_i64; 2], |sum, member| [sum[0] + member[0] as i64, sum[1] + member[1] as i64]);
+			*center = [(sum[0] / count) as i32, (sum[1] / count) as i32];
+		}
+	}
+
+	(centers, clusters)
+}
+
+// k-means on the plants, starting with one cluster around each robot. Seeds go to the nearest cluster.
+pub fn partition(input: &Input) -> Vec<Assignment>
+{
+	let robots = input.robots();
+	let distances = Distances::new(input);
+
+	let (centers, clusters) = k_means(&distances, &input.plants, robots.iter().map(|robot| robot.start).collect());
+
+	let mut assignments = vec![Assignment::default(); robots.len()];
+
+	for (&plant, &cluster) in input.plants.iter().zip(&clusters)
+	{
+		assignments[cluster].plants.push(plant);
+	}
+
+	for &seed in &input.seeds
+	{
+		assignments[nearest(&distances, &centers, seed)].seeds.push(seed);
+	}
+
+	assignments
+}
+
+
+// Plan of each robot, in world coordinates
+pub fn route(input: &Input, assignments: &[Assignment], constructor: &impl Fn(&Input) -> Vec<Action>) -> Vec<Vec<Action>>
+{
+	input.robots().iter()
+		.zip(assignments)
+		.map(|(robot, assignment)| constructor(&robot_input(input, robot, assignment)))
+		.collect()
+}
+
+// Total plant count, planted value and distance of the fleet
+pub fn evaluate(input: &Input, plans: &[Vec<Action>]) -> (usize, u32, i32)
+{
+	input.robots().iter()
+		.zip(plans)
+		.map(|(robot, actions)|
+		{
+			let (_, plant_count, distance_traveled) = resolve_robot(input, robot, actions);
+			(plant_count, planted_value(input, actions, plant_count), distance_traveled)
+		})
+		.fold((0, 0, 0), |(a, b, c), (d, e, f)| (a + d, b + e, c + f))
+}
+
+
+// Planted value and distance of one robot
+fn robot_score(input: &Input, robot: &Robot, actions: &[Action]) -> (u32, i32)
+{
+	let (_, plant_count, distance_traveled) = resolve_robot(input, robot, actions);
+	(planted_value(input, actions, plant_count), distance_traveled)
+}
+
+// Move the plants a robot cannot reach to the robot with the closest plant or seed, while it improves the fleet and
+// for at most `REBALANCE_PASSES` passes. A move only changes two robots, only their plans are built and evaluated.
+pub fn rebalance(input: &Input, assignments: &mut [Assignment], plans: &mut [Vec<Action>], constructor: &impl Fn(&Input) -> Vec<Action>)
+{
+	let robots = input.robots();
+	let distances = Distances::new(input);
+
+	let mut scores: Vec<(u32, i32)> = robots.iter().zip(plans.iter()).map(|(robot, actions)| robot_score(input, robot, actions)).collect();
+
+	for _ in 0..REBALANCE_PASSES
+	{
+		let mut improved = false;
+
+		for from in 0..robots.len()
+		{
+			let (_, planted, _) = resolve_robot(input, &robots[from], &plans[from]);
+
+			let planted: HashSet<[i32; 2]> = plans[from].iter()
+				.filter(|action| action.kind == ActionKind::Plant)
+				.take(planted)
+				.map(|action| action.pos)
+				.collect();
+
+			let missing: Vec<[i32; 2]> = assignments[from].plants.iter()
+				.copied()
+				.filter(|plant| !planted.contains(plant))
+				.collect();
+
+			for plant in missing
+			{
+				let mut targets: Vec<usize> = (0..robots.len()).filter(|&to| to != from).collect();
+				targets.sort_unstable_by_key(|&to|
+				{
+					assignments[to].plants.iter()
+						.chain(&assignments[to].seeds)
+						.chain([&robots[to].start])
+						.map(|&pos| distances.distance(pos, plant))
+						.min()
+				});
+
+				for to in targets
+				{
+					let mut from_assignment = assignments[from].clone();
+					from_assignment.plants.retain(|&other| other != plant);
+					let mut to_assignment = assignments[to].clone();
+					to_assignment.plants.push(plant);
+
+					let from_plan = constructor(&robot_input(input, &robots[from], &from_assignment));
+					let to_plan = constructor(&robot_input(input, &robots[to], &to_assignment));
+
+					let from_score = robot_score(input, &robots[from], &from_plan);
+					let to_score = robot_score(input, &robots[to], &to_plan);
+
+					// The other robots do not change, comparing the two robots compares the fleets
+					let old = (scores[from].0 + scores[to].0, -(scores[from].1 + scores[to].1));
+					let new = (from_score.0 + to_score.0, -(from_score.1 + to_score.1));
+
+					if new > old
+					{
+						assignments[from] = from_assignment;
+						assignments[to] = to_assignment;
+						plans[from] = from_plan;
+						plans[to] = to_plan;
+						scores[from] = from_score;
+						scores[to] = to_score;
+						improved = true;
+						break;
+					}
+				}
+			}
+		}
+
+		if !improved
+		{
+			break;
+		}
+	}
+}
+
+
+// Cluster first, route each robot with the constructor, then rebalance the plants between robots
+pub fn solve(input: &Input, constructor: impl Fn(&Input) -> Vec<Action>) -> Vec<Vec<Action>>
+{
+	let mut assignments = partition(input);
+	let mut plans = route(input, &assignments, &constructor);
+
+	rebalance(input, &mut assignments, &mut plans, &constructor);
+
+	plans
+}
@@ -0,0 +1,270 @@
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+
+use crate::io::Input;
+use crate::{score, Action, ActionKind};
+
+
+// Individuals are orders of the genes: every plant then every seed pile of the input, by index
+type Individual = (u32, i32, Vec<usize>);
+
+fn genes(input: &Input) -> Vec<Action>
+{
+	input.plants.iter()
+		.map(|&pos| Action { pos, kind: ActionKind::Plant })
+		.chain(input.seeds.iter().map(|&pos| Action { pos, kind: ActionKind::Collect }))
+		.collect()
+}
+
+// Actions of an order with the seed stock repaired: a plant without seeds pulls the next collect of the order
+// before it, the plants left are dropped when there is no collect left
+fn decode(input: &Input, genes: &[Action], order: &[usize]) -> Vec<Action>
+{
+	let mut seed_storage = input.seed_capacity;
+	let mut pulled = vec![false; genes.len()];
+
+	let mut actions = Vec::new();
+
+	for (i, &gene) in order.iter().enumerate()
+	{
+		let action = genes[gene];
+
+		match action.kind
+		{
+			ActionKind::Collect =>
+			{
+				if !pulled[gene]
+				{
+					actions.push(action);
+					seed_storage = input.seed_capacity;
+				}
+			},
+			ActionKind::Plant =>
+			{
+				if seed_storage == 0
+				{
+					let Some(&next) = order[i+1..].iter().find(|&&next| genes[next].kind == ActionKind::Collect && !pulled[next])
+					else
+					{
+						continue;
+					};
+
+					pulled[next] = true;
+					actions.push(genes[next]);
+					seed_storage = input.seed_capacity;
+				}
+
+				if seed_storage > 0
+				{
+					actions.push(action);
+					seed_storage -= 1;
+				}
+			},
+		}
+	}
+
+	actions
+}
+
+// Order of the genes following a plan, the genes the plan does not use go at the end
+fn encode(genes: &[Action], actions: &[Action]) -> Vec<usize>
+{
+	let mut used = vec![false; genes.len()];
+	let mut order = Vec::new();
+
+	for action in actions
+	{
+		if let Some(gene) = (0..genes.len()).find(|&gene| !used[gene] && genes[gene] == *action)
+		{
+			used[gene] = true;
+			order.push(gene);
+		}
+	}
+
+	order.extend((0..genes.len()).filter(|&gene| !used[gene]));
+	order
+}
+
+fn evaluate(input: &Input, genes: &[Action], order: Vec<usize>) -> Individual
+{
+	let actions = decode(input, genes, &order);
+	let (value, neg_distance) = score(input, &actions);
+	(value, neg_distance, order)
+}
+
+
+// OX: a slice of the first parent in place, the other genes in the order of the second parent
+fn order_crossover<R: Rng>(a: &[usize], b: &[usize], rng: &mut R) -> Vec<usize>
+{
+	let start = rng.random_range(0..a.len());
+	let end = rng.random_range(start..=a.len());
+
+	let mut taken = vec![false; a.len()];
+	for &gene in &a[start..end]
+	{
+		taken[gene] = true;
+	}
+
+	let mut rest = b.iter().copied().filter(|&gene| !taken[gene]);
+
+	let mut child: Vec<usize> = rest.by_ref().take(start).collect();
+	child.extend_from_slice(&a[start..end]);
+	child.extend(rest);
+	child
+}
+
+// Edge recombination: the next gene is a neighbour of the current one in a parent, the one with the fewest
+// neighbours left, or a random gene left when it has none
+fn edge_recombination<R: Rng>(a: &[usize], b: &[usize], rng: &mut R) -> Vec<usize>
+{
+	let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); a.len()];
+
+	for parent in [a, b]
+	{
+		for window in parent.windows(2)
+		{
+			for (from, to) in [(window[0], window[1]), (window[1], window[0])]
+			{
+				if !neighbours[from].contains(&to)
+				{
+					neighbours[from].push(to);
+				}
+			}
+		}
+	}
+
+	// Genes left and the index of each one in `left`
+	let mut left: Vec<usize> = a.to_vec();
+	let mut positions = vec![0; a.len()];
+	for (index, &gene) in left.iter().enumerate()
+	{
+		positions[gene] = index;
+	}
+
+	let mut gene = a[0];
+	let mut child = Vec::with_capacity(a.len());
+
+	loop
+	{
+		child.push(gene);
+
+		let index = positions[gene];
+		left.swap_remove(index);
+		if index < left.len()
+		{
+			positions[left[index]] = index;
+		}
+
+		// Only the neighbours of the gene have it in their lists
+		for neighbour in neighbours[gene].clone()
+		{
+			neighbours[neighbour].retain(|&other| other != gene);
+		}
+
+		if left.is_empty()
+		{
+			break;
+		}
+
+		let candidates = &neighbours[gene];
+
+		gene = match candidates.iter().map(|&candidate| neighbours[candidate].len()).min()
+		{
+			Some(fewest) =>
+			{
+				let best: Vec<usize> = candidates.iter().copied().filter(|&candidate| neighbours[candidate].len() == fewest).collect();
+				*best.choose(rng).unwrap()
+			},
+			None => *left.choose(rng).unwrap(),
+		};
+	}
+
+	child
+}
+
+fn mutate<R: Rng>(order: &mut [usize], rng: &mut R)
+{
+	if order.len() < 2
+	{
+		return;
+	}
+
+	if rng.random_bool(0.3)
+	{
+		let i = rng.random_range(0..order.len());
+		let j = rng.random_range(0..order.len());
+		order.swap(i, j);
+	}
+
+	if rng.random_bool(0.3)
+	{
+		let start = rng.random_range(0..order.len());
+		let end = rng.random_range(start..=order.len());
+		let len = end - start;
+
+		if len > 1
+		{
+			order[start..end].rotate_left(rng.random_range(1..len));
+		}
+	}
+}
+
+fn tournament<'l, R: Rng>(population: &'l [Individual], rng: &mut R) -> &'l [usize]
+{
+	let winner = (0..3)
+		.map(|_| &population[rng.random_range(0..population.len())])
+		.max_by_key(|(value, neg_distance, _)| (*value, *neg_distance))
+		.unwrap();
+
+	&winner.2
+}
+
+
+// Genetic algorithm over orders of every plant and seed, scored with `resolve_q_fast` on the repaired actions.
+// The initial population holds the `initial` plans, the rest is random.
+pub fn genetic<R: Rng>(input: &Input, initial: &[Vec<Action>], population_size: usize, generations: usize, rng: &mut R) -> Vec<Action>
+{
+	let genes = genes(input);
+
+	if genes.is_empty()
+	{
+		return Vec::new();
+	}
+
+	let population_size = population_size.max(initial.len()).max(2);
+
+	let mut population: Vec<Individual> = initial.iter()
+		.map(|actions| evaluate(input, &genes, encode(&genes, actions)))
+		.collect();
+
+	while population.len() < population_size
+	{
+		let mut order: Vec<usize> = (0..genes.len()).collect();
+		order.shuffle(rng);
+		population.push(evaluate(input, &genes, order));
+	}
+
+	for _generation in 0..generations
+	{
+		population.sort_by_key(|(value, neg_distance, _)| (std::cmp::Reverse(*value), std::cmp::Reverse(*neg_distance)));
+
+		// The two best go through unchanged
+		let mut next = population[..2].to_vec();
+
+		while next.len() < population_size
+		{
+			let a = tournament(&population, rng);
+			let b = tournament(&population, rng);
+
+			let mut child = if rng.random_bool(0.5) { order_crossover(a, b, rng) } else { edge_recombination(a, b, rng) };
+			mutate(&mut child, rng);
+
+			next.push(evaluate(input, &genes, child));
+		}
+
+		population = next;
+	}
+
+	let (_, _, best) = population.into_iter().max_by_key(|(value, neg_distance, _)| (*value, *neg_distance)).unwrap();
+	decode(input, &genes, &best)
+}
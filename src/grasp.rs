@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::cell_set::CellSet;
+use crate::greedy::{cost_per_value, State};
+use crate::io::Input;
+use crate::iterated::Improver;
+use crate::metric::PositionSet;
+use crate::quantum::QPos;
+use crate::{score, Action, ActionKind};
+
+
+#[derive(Debug, Copy, Clone)]
+pub struct Config
+{
+	// Fraction of the candidates the next action is drawn from, 0 is the nearest neighbour
+	pub alpha: f64,
+	pub time_limit: Duration,
+	pub max_iterations: usize,
+}
+
+// Spread of the (value, -distance) scores of the improved plans
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Statistics
+{
+	pub iterations: usize,
+	pub best: (u32, i32),
+	pub worst: (u32, i32),
+	pub mean_value: f64,
+	pub value_deviation: f64,
+	pub mean_distance: f64,
+}
+
+impl Statistics
+{
+	fn new(scores: &[(u32, i32)]) -> Self
+	{
+		let Some(&best) = scores.iter().max()
+		else
+		{
+			return Statistics::default();
+		};
+
+		let count = scores.len() as f64;
+		let mean_value = scores.iter().map(|&(value, _)| value as f64).sum::<f64>() / count;
+		let variance = scores.iter().map(|&(value, _)| (value as f64 - mean_value).powi(2)).sum::<f64>() / count;
+
+		Statistics
+		{
+			iterations: scores.len(),
+			best,
+			worst: *scores.iter().min().unwrap(),
+			mean_value,
+			value_deviation: variance.sqrt(),
+			mean_distance: scores.iter().map(|&(_, neg_distance)| -neg_distance as f64).sum::<f64>() / count,
+		}
+	}
+}
+
+
+// Nearest neighbour where the next plant, or seed when no seed fits a plant left, is drawn among the best `alpha`
+// fraction of the ones within the distance left, by distance per unit of value. The seeds follow the species and
+// the pile sizes like the greedy.
+pub fn construct<R: Rng>(input: &Input, alpha: f64, rng: &mut R) -> Vec<Action>
+{
+	if input.exact_qpos()
+	{
+		construct_with::<QPos, R>(input, alpha, rng)
+	}
+	else
+	{
+		construct_with::<CellSet, R>(input, alpha, rng)
+	}
+}
+
+fn construct_with<P: PositionSet, R: Rng>(input: &Input, alpha: f64, rng: &mut R) -> Vec<Action>
+{
+	let mut pos = P::from_cell(input.start);
+	let mut state = State::new(input);
+	let mut distance_traveled = 0;
+
+	let mut actions = Vec::new();
+
+	while !state.plants.is_empty()
+	{
+		let kind = if state.can_plant(input) { ActionKind::Plant } else { ActionKind::Collect };
+
+		if kind == ActionKind::Collect && state.inventory.is_full(input)
+		{
+			break;
+		}
+
+		let targets = if kind == ActionKind::Plant { &state.plants } else { &state.seeds };
+
+		let mut candidates: Vec<(usize, P, i32, i32, u32)> = targets.iter()
+			.enumerate()
+			.filter_map(|(index, &target)|
+			{
+				let action = Action { pos: target, kind };
+				let (new_pos, dist) = pos.apply_action(input, &action);
+
+				// With species, a seed pile is ranked with the way to the next plant it can be used for
+				let (rank, value) = match kind
+				{
+					ActionKind::Plant if !state.inventory.can_plant(input, target) => return None,
+					ActionKind::Plant => (dist, input.plant_value(target)),
+					ActionKind::Collect => (dist + state.seed_detour(input, target)?, 1),
+				};
+
+				Some((index, new_pos, dist, rank, value))
+			})
+			.filter(|(_, new_pos, dist, _, _)| distance_traveled + dist + new_pos.return_distance(input) <= input.max_distance as i32)
+			.collect();
+
+		candidates.sort_by(|&(a_index, _, _, a, a_value), &(b_index, _, _, b, b_value)| cost_per_value((a, a_value), (b, b_value)).then(a_index.cmp(&b_index)));
+
+		let restricted = ((alpha * candidates.len() as f64).ceil() as usize).max(1).min(candidates.len());
+
+		let Some((index, new_pos, dist, _, _)) = candidates[..restricted].choose(rng).cloned()
+		else
+		{
+			break;
+		};
+
+		actions.push(Action { pos: targets[index], kind });
+
+		match kind
+		{
+			ActionKind::Plant =>
+			{
+				let plant = state.plants.remove(index);
+				state.inventory.plant(input, plant);
+			},
+			ActionKind::Collect => state.collect(input, index),
+		}
+
+		pos = new_pos;
+		distance_traveled += dist;
+	}
+
+	actions
+}
+
+
+// GRASP: randomized constructions improved by the local search, until the time limit or the iteration count.
+// Returns the best plan and the statistics of all the improved plans.
+pub fn grasp<I: Improver, R: Rng>(input: &Input, improver: &mut I, config: Config, rng: &mut R) -> (Vec<Action>, Statistics)
+{
+	let start = Instant::now();
+
+	let mut best: Option<((u32, i32), Vec<Action>)> = None;
+	let mut scores = Vec::new();
+
+	while scores.len() < config.max_iterations && start.elapsed() < config.time_limit
+	{
+		let mut actions = construct(input, config.alpha, rng);
+		improver.improve(input, &mut actions);
+
+		let actions_score = score(input, &actions);
+		scores.push(actions_score);
+
+		if best.as_ref().is_none_or(|(best_score, _)| actions_score > *best_score)
+		{
+			best = Some((actions_score, actions));
+		}
+	}
+
+	(best.map(|(_, actions)| actions).unwrap_or_default(), Statistics::new(&scores))
+}
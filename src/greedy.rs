@@ -0,0 +1,290 @@
+use std::cmp::Ordering;
+
+use crate::io::Input;
+use crate::piles::{pickup, pile_sizes};
+use crate::species::Inventory;
+use crate::{Action, ActionKind};
+
+// Robot position, seeds carried and what is left on the field, shared with the constructors that follow the species
+// and the pile sizes like the greedy
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct State
+{
+	pub robot_pos: [i32; 2],
+	pub inventory: Inventory,
+	pub seeds: Vec<[i32;2]>,
+	// Seeds left in each pile of `seeds`
+	pub piles: Vec<u32>,
+	pub plants: Vec<[i32;2]>,
+}
+
+impl State
+{
+	pub fn new(input: &Input) -> Self
+	{
+		State
+		{
+			robot_pos: input.start,
+			inventory: Inventory::new(input),
+			seeds: input.seeds.clone(),
+			piles: pile_sizes(input),
+			plants: input.plants.clone(),
+		}
+	}
+
+	// The robot plants when it has a seed for one of the plants left, otherwise it collects
+	pub fn can_plant(&self, input: &Input) -> bool
+	{
+		!self.inventory.is_empty() && self.plants.iter().any(|&plant| self.inventory.can_plant(input, plant))
+	}
+
+	// Take the seeds of the pile `index`, a pile is only visited again when it has seeds left
+	pub fn collect(&mut self, input: &Input, index: usize)
+	{
+		let seed_pos = self.seeds[index];
+		let species = input.seed_species(seed_pos);
+		let needed = self.plants.iter().filter(|&&plant| input.accepts(plant, species)).count() as u32;
+
+		let (count, left) = pickup(input, self.inventory.total(), self.piles[index], needed);
+
+		self.robot_pos = seed_pos;
+		self.inventory.add(input, seed_pos, count);
+		self.piles[index] = left;
+
+		if !input.has_piles() || left == 0
+		{
+			self.seeds.remove(index);
+			self.piles.remove(index);
+		}
+	}
+
+	// Distance from the seed pile to the nearest plant left accepting its species, `None` if no plant does
+	pub fn seed_detour(&self, input: &Input, seed_pos: [i32; 2]) -> Option<i32>
+	{
+		if !input.has_species()
+		{
+			return Some(0);
+		}
+
+		let species = input.seed_species(seed_pos);
+
+		self.plants.iter()
+			.filter(|&&plant| input.accepts(plant, species))
+			.map(|&plant| input.travel_distance(seed_pos, plant))
+			.min()
+	}
+}
+
+
+// Always go to the nearest plant, or to the nearest seed when the storage is empty.
+// With weighted plants, the plant with the lowest distance per unit of value is the nearest.
+// When the robot must return to base, the plants it could not come back from are skipped.
+// With pile sizes, the robot takes what it can plant and comes back to a pile while it has seeds.
+pub fn nearest_neighbour(input: &Input) -> Vec<Action>
+{
+	let mut state = State::new(input);
+	let mut distance_traveled = 0;
+
+	let mut moves = Vec::new();
+
+	while !state.plants.is_empty()
+	{
+		let pos = state.robot_pos;
+
+		if state.can_plant(input)
+		{
+			let Some((min_plant_index, min_plant_pos)) = state.plants.iter()
+				.enumerate()
+				.filter(|(_, plant)| state.inventory.can_plant(input, **plant))
+				.filter(|(_, plant)| !input.return_to_base || distance_traveled + input.travel_distance(pos, **plant) + input.return_distance(**plant) <= input.max_distance as i32)
+				.min_by(|(_, a), (_, b)| cost_per_value((input.travel_distance(pos, **a), input.plant_value(**a)), (input.travel_distance(pos, **b), input.plant_value(**b))))
+			else
+			{
+				break;
+			};
+
+			let min_plant_pos = *min_plant_pos;
+			distance_traveled += input.travel_distance(pos, min_plant_pos);
+
+			moves.push(Action
+			{
+				pos: min_plant_pos,
+				kind: ActionKind::Plant
+			});
+
+			state.robot_pos = min_plant_pos;
+			state.inventory.plant(input, min_plant_pos);
+			state.plants.remove(min_plant_index);
+		}
+		else
+		{
+			if state.inventory.is_full(input)
+			{
+				break;
+			}
+
+			// With species, the seed pile is chosen with the way to the next plant it can be used for.
+			// The plan ends when no pile is left, the greedy bins used to panic there.
+			let Some((min_seed_index, min_seed_pos, _)) = state.seeds.iter()
+				.enumerate()
+				.filter_map(|(index, seed)| Some((index, seed, input.travel_distance(pos, *seed) + state.seed_detour(input, *seed)?)))
+				.min_by_key(|(_, _, dist)| *dist)
+			else
+			{
+				break;
+			};
+
+			let min_seed_pos = *min_seed_pos;
+			distance_traveled += input.travel_distance(pos, min_seed_pos);
+
+			moves.push(Action
+			{
+				pos: min_seed_pos,
+				kind: ActionKind::Collect
+			});
+
+			state.collect(input, min_seed_index);
+		}
+	}
+
+	moves
+}
+
+
+// Compares (distance, value) pairs by distance per unit of value, then by distance.
+// This is the plain distance when every plant is worth the same.
+pub fn cost_per_value(a: (i32, u32), b: (i32, u32)) -> Ordering
+{
+	((a.0 as i64 + 1) * b.1 as i64).cmp(&((b.0 as i64 + 1) * a.1 as i64))
+		.then(a.0.cmp(&b.0))
+}
+
+
+// The lookahead totals are ranked by distance per unit of value when the plants are worth different values, by plain
+// distance otherwise: with equal values a lookahead planting more must not win for it
+fn lookahead_order(weighted: bool, a: (i32, u32), b: (i32, u32)) -> Ordering
+{
+	if weighted { cost_per_value(a, b) } else { a.0.cmp(&b.0) }
+}
+
+// Best next action with the distance and the value of the lookahead
+fn find_best_action(input: &Input, state: &State, depth: u32, weighted: bool) -> Option<(i32, u32, usize, Action)>
+{
+	if state.plants.is_empty()
+	{
+		return None;
+	}
+
+	let pos = state.robot_pos;
+
+	if state.can_plant(input)
+	{
+		let (min_dist, value, min_plant_index, min_plant_pos) = state.plants.iter()
+			.enumerate()
+			.filter(|(_, plant)| state.inventory.can_plant(input, **plant))
+			.map(|(index, plant)|
+			{
+				let dist = input.travel_distance(pos, *plant);
+				let value = input.plant_value(*plant);
+
+				if depth <= 1
+				{
+					return (dist + input.return_distance(*plant), value, index, plant);
+				}
+
+				let mut new_state = state.clone();
+				new_state.inventory.plant(input, *plant);
+				new_state.robot_pos = *plant;
+				new_state.plants.remove(index);
+
+				if let Some((min_dist, min_value, _, _)) = find_best_action(input, &new_state, depth-1, weighted)
+				{
+					(dist + min_dist, value + min_value, index, plant)
+				}
+				else
+				{
+					(dist + input.return_distance(*plant), value, index, plant)
+				}
+			})
+			.min_by(|a, b| lookahead_order(weighted, (a.0, a.1), (b.0, b.1)))
+			.unwrap();
+
+		Some((min_dist, value, min_plant_index, Action
+		{
+			pos: *min_plant_pos,
+			kind: ActionKind::Plant
+		}))
+	}
+	else if !state.inventory.is_full(input)
+	{
+		let (min_dist, value, min_seed_index, min_seed_pos) = state.seeds.iter()
+			.enumerate()
+			.filter_map(|(index, seed)| Some((index, seed, state.seed_detour(input, *seed)?)))
+			.map(|(index, seed, detour)|
+			{
+				let dist = input.travel_distance(pos, *seed);
+
+				if depth <= 1
+				{
+					return (dist + detour + input.return_distance(*seed), 0, index, seed);
+				}
+
+				let mut new_state = state.clone();
+				new_state.collect(input, index);
+
+				if let Some((min_dist, min_value, _, _)) = find_best_action(input, &new_state, depth-1, weighted)
+				{
+					(dist + min_dist, min_value, index, seed)
+				}
+				else
+				{
+					(dist + input.return_distance(*seed), 0, index, seed)
+				}
+			})
+			.min_by(|a, b| lookahead_order(weighted, (a.0, a.1), (b.0, b.1)))?;
+
+		Some((min_dist, value, min_seed_index, Action
+		{
+			pos: *min_seed_pos,
+			kind: ActionKind::Collect
+		}))
+	}
+	else
+	{
+		None
+	}
+}
+
+
+// Greedy with a lookahead of `depth` actions, the return leg is counted at the end of the lookahead
+pub fn limited_search(input: &Input, depth: u32) -> Vec<Action>
+{
+	let mut state = State::new(input);
+	let weighted = input.values.windows(2).any(|pair| pair[0] != pair[1]);
+
+	let mut moves = Vec::new();
+
+	while !state.plants.is_empty()
+	{
+		let Some((_dist, _value, index, action)) = find_best_action(input, &state, depth, weighted)
+		else
+		{
+			break;
+		};
+
+		moves.push(action);
+		state.robot_pos = action.pos;
+
+		match action.kind
+		{
+			ActionKind::Plant =>
+			{
+				state.inventory.plant(input, action.pos);
+				state.plants.remove(index);
+			},
+			ActionKind::Collect => state.collect(input, index),
+		}
+	}
+
+	moves
+}
@@ -1,12 +1,16 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter};
+use std::sync::Arc;
 use std::fmt::Write;
 
 use serde::Deserialize;
 
+use crate::metric::Metric;
+use crate::obstacles::{self, Obstacle, PathCache};
 use crate::Action;
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct Input
 {
 	#[serde(rename(deserialize = "maxDistance"))]
@@ -16,9 +20,260 @@ pub struct Input
 	pub range: i32,
 	pub seeds: Vec<[i32;2]>,
 	pub plants: Vec<[i32;2]>,
+	// Value of each plant, every plant is worth 1 when empty
+	#[serde(default)]
+	pub values: Vec<u32>,
+	// Species of each seed pile, the seeds have no species when empty
+	#[serde(default)]
+	pub species: Vec<u32>,
+	// Species accepted by each plant, a plant without requirements accepts every species
+	#[serde(default)]
+	pub requirements: Vec<Vec<u32>>,
+	// Seeds in each pile. When empty, each pile refills the robot once.
+	#[serde(default, rename(deserialize = "pileSizes"))]
+	pub pile_sizes: Vec<u32>,
+	// Time window [open, close] of each plant, one time unit per cell moved
+	#[serde(default)]
+	pub windows: Vec<[u32; 2]>,
+	#[serde(default, rename(deserialize = "plantDuration"))]
+	pub plant_duration: u32,
+	#[serde(default, rename(deserialize = "collectDuration"))]
+	pub collect_duration: u32,
+	// Probability that each seed pile is empty when the robot gets there
+	#[serde(default, rename(deserialize = "failureProbabilities"))]
+	pub failure_probabilities: Vec<f64>,
+	#[serde(default)]
+	pub robots: Vec<Robot>,
+	#[serde(default)]
+	pub obstacles: Vec<Obstacle>,
+	#[serde(default)]
+	pub metric: Metric,
+	#[serde(default)]
+	pub start: [i32; 2],
+	// The robot must be back at the start at the end, within the maximum distance
+	#[serde(default, rename(deserialize = "returnToBase"))]
+	pub return_to_base: bool,
+	// Index of the first plant and of the first seed pile at each position, built by `index`.
+	// A lookup checks the index it finds, an index left behind by a change of the plants only falls back to a scan.
+	#[serde(skip)]
+	pub plant_indices: HashMap<[i32; 2], usize>,
+	#[serde(skip)]
+	pub seed_indices: HashMap<[i32; 2], usize>,
+	// Number of plants accepting each species of the seed piles, built by `index`
+	#[serde(skip)]
+	pub species_demand: HashMap<u32, usize>,
+	// Shortest paths around the obstacles, built by `index` and shared by the clones of the input
+	#[serde(skip)]
+	pub path_cache: Option<Arc<PathCache>>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Robot
+{
+	pub start: [i32; 2],
+	#[serde(rename(deserialize = "maxDistance"))]
+	pub max_distance: u32,
+	#[serde(rename(deserialize = "seedCapacity"))]
+	pub seed_capacity: u32,
+}
+
+fn first_indices(positions: &[[i32; 2]]) -> HashMap<[i32; 2], usize>
+{
+	let mut indices = HashMap::new();
+
+	for (index, &pos) in positions.iter().enumerate()
+	{
+		indices.entry(pos).or_insert(index);
+	}
+
+	indices
+}
+
+fn find(indices: &HashMap<[i32; 2], usize>, positions: &[[i32; 2]], pos: [i32; 2]) -> Option<usize>
+{
+	match indices.get(&pos)
+	{
+		Some(&index) if positions.get(index) == Some(&pos) => Some(index),
+		_ => positions.iter().position(|&other| other == pos),
+	}
+}
+
+impl Input
+{
+	// Index the plants and the seed piles by position and share a cache of the paths around the obstacles, once they are known
+	pub fn index(&mut self)
+	{
+		self.plant_indices = first_indices(&self.plants);
+		self.seed_indices = first_indices(&self.seeds);
+
+		if self.obstacles.is_empty()
+		{
+			self.path_cache = None;
+		}
+		else if !self.path_cache.as_ref().is_some_and(|cache| cache.matches(self))
+		{
+			self.path_cache = Some(Arc::new(PathCache::new(self)));
+		}
+
+		self.species_demand = HashMap::new();
+		for &species in &self.species
+		{
+			self.species_demand.entry(species).or_insert(0);
+		}
+
+		for &plant in &self.plants
+		{
+			let requirements = self.plant_index(plant).and_then(|index| self.requirements.get(index)).filter(|requirements| !requirements.is_empty());
+
+			for (&species, demand) in self.species_demand.iter_mut()
+			{
+				if requirements.is_none_or(|requirements| requirements.contains(&species))
+				{
+					*demand += 1;
+				}
+			}
+		}
+	}
+
+	// Number of plants accepting the species, the input is only scanned when it was not indexed
+	pub fn demand(&self, species: u32) -> usize
+	{
+		match self.species_demand.get(&species)
+		{
+			Some(&demand) => demand,
+			None => self.plants.iter().filter(|&&plant| self.accepts(plant, Some(species))).count(),
+		}
+	}
+
+	pub fn plant_index(&self, pos: [i32; 2]) -> Option<usize>
+	{
+		find(&self.plant_indices, &self.plants, pos)
+	}
+
+	pub fn seed_index(&self, pos: [i32; 2]) -> Option<usize>
+	{
+		find(&self.seed_indices, &self.seeds, pos)
+	}
+
+	// The robot list, or the single robot described by the input when there is none
+	pub fn robots(&self) -> Vec<Robot>
+	{
+		if self.robots.is_empty()
+		{
+			vec![Robot
+			{
+				start: self.start,
+				max_distance: self.max_distance,
+				seed_capacity: self.seed_capacity,
+			}]
+		}
+		else
+		{
+			self.robots.clone()
+		}
+	}
+
+	// Distance to walk from a to b with the metric of the input, around the obstacles if there are any
+	pub fn travel_distance(&self, a: [i32; 2], b: [i32; 2]) -> i32
+	{
+		if self.obstacles.is_empty()
+		{
+			self.metric.distance(a, b)
+		}
+		else
+		{
+			obstacles::distance(self, a, b)
+		}
+	}
+
+	pub fn is_weighted(&self) -> bool
+	{
+		!self.values.is_empty()
+	}
+
+	// Value of the plant at `pos`, the first one if several plants share the position
+	pub fn plant_value(&self, pos: [i32; 2]) -> u32
+	{
+		if self.is_weighted()
+		{
+			self.plant_index(pos).map_or(0, |index| self.values[index])
+		}
+		else
+		{
+			1
+		}
+	}
+
+	pub fn has_windows(&self) -> bool
+	{
+		!self.windows.is_empty()
+	}
+
+	// Time window of the plant at `pos`, always open without windows
+	pub fn window(&self, pos: [i32; 2]) -> [u32; 2]
+	{
+		self.plant_index(pos)
+			.and_then(|index| self.windows.get(index))
+			.copied()
+			.unwrap_or([0, u32::MAX])
+	}
+
+	pub fn has_species(&self) -> bool
+	{
+		!self.species.is_empty()
+	}
+
+	// Species of the seed pile at `pos`
+	pub fn seed_species(&self, pos: [i32; 2]) -> Option<u32>
+	{
+		if !self.has_species()
+		{
+			return None;
+		}
+
+		self.seed_index(pos).map(|index| self.species[index])
+	}
+
+	// Seeds without species fit every plant
+	pub fn accepts(&self, plant_pos: [i32; 2], species: Option<u32>) -> bool
+	{
+		let Some(species) = species
+		else
+		{
+			return true;
+		};
+
+		self.plant_index(plant_pos)
+			.and_then(|index| self.requirements.get(index))
+			.is_none_or(|requirements| requirements.is_empty() || requirements.contains(&species))
+	}
+
+	pub fn has_piles(&self) -> bool
+	{
+		!self.pile_sizes.is_empty()
+	}
+
+	// The quadrant position sets `QPos` and `QRect` are only exact for the Manhattan metric without obstacles
+	pub fn exact_qpos(&self) -> bool
+	{
+		self.metric == Metric::Manhattan && self.obstacles.is_empty()
+	}
+
+	// Distance of the return leg from `pos`, zero when the robot may end anywhere
+	pub fn return_distance(&self, pos: [i32; 2]) -> i32
+	{
+		if self.return_to_base
+		{
+			self.travel_distance(pos, self.start)
+		}
+		else
+		{
+			0
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutAction
 {
 	Move([i32;2]),
@@ -38,17 +293,15 @@ pub fn read_input() -> serde_json::Result<Input>
 {
 	let file_name = arg_file_name();
 	let reader = BufReader::new(File::open(format!("input/{file_name}.json")).unwrap());
-	serde_json::from_reader(reader)
-}
 
-/*pub fn read_output() -> Vec<OutAction>
-{
-	let file_name = arg_file_name();
-	let exe_name = std::env::args().nth(2).unwrap();
-	let reader = BufReader::new(File::open(format!("output/{file_name}_{exe_name}.json")).unwrap());
+	let mut input: Input = serde_json::from_reader(reader)?;
+	input.index();
 
-	let moves_str: Vec<String> = serde_json::from_reader(reader).unwrap();
+	Ok(input)
+}
 
+pub fn parse_output(moves_str: &[String]) -> Vec<OutAction>
+{
 	let mut actions = Vec::new();
 
 	for action_str in moves_str
@@ -75,7 +328,19 @@ pub fn read_input() -> serde_json::Result<Input>
 	}
 
 	actions
-}*/
+}
+
+pub fn format_output(out_actions: &[OutAction]) -> Vec<String>
+{
+	out_actions.iter()
+		.map(|action| match action
+		{
+			OutAction::Move(pos) => format!("MOVE {} {}", pos[0], pos[1]),
+			OutAction::Plant(pos) => format!("PLANT {} {}", pos[0], pos[1]),
+			OutAction::Collect => "COLLECT".to_string(),
+		})
+		.collect()
+}
 
 pub fn read_output() -> Vec<Action>
 {
@@ -87,25 +352,66 @@ pub fn read_output() -> Vec<Action>
 	serde_json::from_reader(reader).unwrap()
 }
 
-pub fn write_output(out_actions: &[OutAction], actions: Option<&[Action]>, plant_count: usize, distance_traveled: i32)
+// Actions recorded in the `.meta` of every solution of the input in `output/`, from any binary
+pub fn read_output_plans() -> Vec<Vec<Action>>
 {
-	println!("Solution found in {} moves", out_actions.len());
+	let prefix = format!("{}_", arg_file_name());
 
-	dbg!(plant_count);
-	dbg!(distance_traveled);
+	let Ok(entries) = std::fs::read_dir("output")
+	else
+	{
+		return Vec::new();
+	};
+
+	let mut paths: Vec<_> = entries.filter_map(Result::ok)
+		.map(|entry| entry.path())
+		.filter(|path| path.extension().is_some_and(|extension| extension == "meta"))
+		.filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with(&prefix)))
+		.collect();
+	paths.sort();
+
+	// The fleet solutions and the metas without actions are skipped
+	paths.into_iter()
+		.filter_map(|path|
+		{
+			let mut reader = BufReader::new(File::open(path).ok()?);
+			reader.skip_until(b'\n').ok()?;
+			serde_json::from_reader(reader).ok()
+		})
+		.collect()
+}
 
+fn output_base_name() -> String
+{
 	let mut exe_name = std::env::current_exe().unwrap().file_stem().unwrap().to_str().unwrap().to_owned();
 	let file_name = arg_file_name();
 	for arg in std::env::args().skip(2)
 	{
 		write!(&mut exe_name, "_{arg}").unwrap();
 	}
-	let output_base_name = format!("output/{file_name}_{exe_name}");
+	format!("output/{file_name}_{exe_name}")
+}
+
+pub fn write_output(out_actions: &[OutAction], actions: Option<&[Action]>, plant_count: usize, distance_traveled: i32)
+{
+	write_scored_output(out_actions, actions, plant_count, plant_count as u32, distance_traveled);
+}
+
+// `write_output` for weighted plants, the meta also records the planted value
+pub fn write_scored_output(out_actions: &[OutAction], actions: Option<&[Action]>, plant_count: usize, value: u32, distance_traveled: i32)
+{
+	println!("Solution found in {} moves", out_actions.len());
+
+	dbg!(plant_count);
+	dbg!(value);
+	dbg!(distance_traveled);
+
+	let output_base_name = output_base_name();
 
 	{
 		let mut buffer = BufWriter::new(File::create(format!("{output_base_name}.meta")).unwrap());
 		use std::io::Write;
-		writeln!(buffer, "{plant_count} {distance_traveled}").unwrap();
+		writeln!(buffer, "{plant_count} {distance_traveled} {value}").unwrap();
 		if let Some(actions) = actions
 		{
 			serde_json::to_writer_pretty(buffer, actions).unwrap();
@@ -113,16 +419,28 @@ pub fn write_output(out_actions: &[OutAction], actions: Option<&[Action]>, plant
 	}
 
 	let buffer = BufWriter::new(File::create(format!("{output_base_name}.json")).unwrap());
-	let mut moves_str = Vec::new();
-	for action in out_actions
+	serde_json::to_writer_pretty(buffer, &format_output(out_actions)).unwrap();
+}
+
+// One command stream per robot
+pub fn write_fleet_output(out_actions: &[Vec<OutAction>], actions: &[Vec<Action>], plant_count: usize, value: u32, distance_traveled: i32)
+{
+	println!("Solution found in {} moves", out_actions.iter().map(|moves| moves.len()).sum::<usize>());
+
+	dbg!(plant_count);
+	dbg!(value);
+	dbg!(distance_traveled);
+
+	let output_base_name = output_base_name();
+
 	{
-		moves_str.push(match action
-		{
-			OutAction::Move(pos) => format!("MOVE {} {}", pos[0], pos[1]),
-			OutAction::Plant(pos) => format!("PLANT {} {}", pos[0], pos[1]),
-			OutAction::Collect => "COLLECT".to_string(),
-		});
+		let mut buffer = BufWriter::new(File::create(format!("{output_base_name}.meta")).unwrap());
+		use std::io::Write;
+		writeln!(buffer, "{plant_count} {distance_traveled} {value}").unwrap();
+		serde_json::to_writer_pretty(buffer, actions).unwrap();
 	}
-	
+
+	let buffer = BufWriter::new(File::create(format!("{output_base_name}.json")).unwrap());
+	let moves_str: Vec<Vec<String>> = out_actions.iter().map(|moves| format_output(moves)).collect();
 	serde_json::to_writer_pretty(buffer, &moves_str).unwrap();
 }
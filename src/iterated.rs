@@ -0,0 +1,145 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::io::Input;
+use crate::{score, seed_stock_holds, Action};
+
+
+const KICK_ATTEMPTS: usize = 100;
+const SHUFFLE_SIZE: usize = 8;
+
+
+// Any hill climber of the library, closures included
+pub trait Improver
+{
+	fn improve(&mut self, input: &Input, actions: &mut [Action]);
+}
+
+impl<F: FnMut(&Input, &mut [Action])> Improver for F
+{
+	fn improve(&mut self, input: &Input, actions: &mut [Action])
+	{
+		self(input, actions)
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kick
+{
+	// A B C D becomes A C B D
+	DoubleBridge,
+	// A random segment of at most SHUFFLE_SIZE actions is shuffled
+	SegmentShuffle,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Acceptance
+{
+	Better,
+	Equal,
+	// Same value with at most this much more distance
+	Worse(i32),
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Config
+{
+	pub iterations: usize,
+	pub kick: Kick,
+	pub acceptance: Acceptance,
+	// Iterations between two checkpoints of the best plan, 0 for none
+	pub checkpoint_every: usize,
+}
+
+
+fn kick<R: Rng>(actions: &mut [Action], kick: Kick, rng: &mut R)
+{
+	let len = actions.len();
+
+	match kick
+	{
+		Kick::DoubleBridge =>
+		{
+			let mut cuts = [rng.random_range(0..=len), rng.random_range(0..=len), rng.random_range(0..=len)];
+			cuts.sort_unstable();
+			let [a, b, c] = cuts;
+
+			// B C becomes C B
+			actions[a..c].rotate_left(b - a);
+		},
+		Kick::SegmentShuffle =>
+		{
+			let start = rng.random_range(0..len);
+			let end = (start + SHUFFLE_SIZE).min(len);
+			actions[start..end].shuffle(rng);
+		},
+	}
+}
+
+fn accepts(acceptance: Acceptance, new: (u32, i32), current: (u32, i32)) -> bool
+{
+	match acceptance
+	{
+		Acceptance::Better => new > current,
+		Acceptance::Equal => new >= current,
+		Acceptance::Worse(slack) => new.0 > current.0 || (new.0 == current.0 && new.1 + slack >= current.1),
+	}
+}
+
+
+// Iterated local search: kick the current plan until the seed stock holds, improve it, and keep it as the current
+// plan when the acceptance allows. The best plan is given to `checkpoint` when it improved since the last one.
+pub fn iterated_local_search<I: Improver, R: Rng, C: FnMut(&[Action])>(input: &Input, actions: &[Action], improver: &mut I, config: Config, rng: &mut R, mut checkpoint: C) -> Vec<Action>
+{
+	let mut current = actions.to_vec();
+	improver.improve(input, &mut current);
+	let mut current_score = score(input, &current);
+
+	let mut best = current.clone();
+	let mut best_score = current_score;
+	let mut saved = false;
+
+	if current.len() < 2
+	{
+		return best;
+	}
+
+	for iteration in 1..=config.iterations
+	{
+		let mut candidate = current.clone();
+
+		let kicked = (0..KICK_ATTEMPTS).any(|_|
+		{
+			candidate.copy_from_slice(&current);
+			kick(&mut candidate, config.kick, rng);
+			seed_stock_holds(input, &candidate)
+		});
+
+		if kicked
+		{
+			improver.improve(input, &mut candidate);
+			let candidate_score = score(input, &candidate);
+
+			if candidate_score > best_score
+			{
+				best = candidate.clone();
+				best_score = candidate_score;
+				saved = false;
+			}
+
+			if accepts(config.acceptance, candidate_score, current_score)
+			{
+				current = candidate;
+				current_score = candidate_score;
+			}
+		}
+
+		if config.checkpoint_every > 0 && iteration % config.checkpoint_every == 0 && !saved
+		{
+			checkpoint(&best);
+			saved = true;
+		}
+	}
+
+	best
+}
@@ -2,22 +2,47 @@ pub mod dijkstra;
 pub mod io;
 pub mod quantum;
 pub mod prim;
+pub mod exact;
+pub mod greedy;
+pub mod pruning;
+pub mod cell_set;
+pub mod realize;
+pub mod fleet;
+pub mod obstacles;
+pub mod metric;
+pub mod species;
+pub mod piles;
+pub mod schedule;
+pub mod online;
+pub mod robust;
+pub mod reseed;
+pub mod savings;
+pub mod genetic;
+pub mod ant_colony;
+pub mod lin_kernighan;
+pub mod iterated;
+pub mod tabu;
+pub mod decompose;
+pub mod coverage;
+pub mod grasp;
 
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
+use cell_set::CellSet;
 use dijkstra::WeightedNode;
 use io::{Input, OutAction};
-use quantum::QPos;
+use metric::{Metric, PositionSet};
+use quantum::QRect;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ActionKind
 {
 	Collect,
 	Plant,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Action
 {
 	pub pos: [i32; 2],
@@ -44,16 +69,151 @@ pub fn distance(a: [i32; 2], b: [i32; 2]) -> i32
 }
 
 
+// Value of the first `plant_count` plants of the actions, the ones kept by `resolve`
+pub fn planted_value(input: &Input, actions: &[Action], plant_count: usize) -> u32
+{
+	actions.iter()
+		.filter(|action| action.kind == ActionKind::Plant)
+		.take(plant_count)
+		.map(|action| input.plant_value(action.pos))
+		.sum()
+}
+
+// Planted value and negated distance of the actions with `resolve_q_fast`, higher is better
+pub fn score(input: &Input, actions: &[Action]) -> (u32, i32)
+{
+	let (plant_count, distance_traveled) = resolve_q_fast(input, actions, true);
+	(planted_value(input, actions, plant_count), -distance_traveled)
+}
+
+// The actions up to the first plant the robot has no seed for or reaches too late, every action when the
+// seeds have no species, the piles never run out and the plants have no time windows
+fn compatible_actions<'l>(input: &Input, actions: &'l [Action]) -> &'l [Action]
+{
+	let actions = if input.has_species() || input.has_piles()
+	{
+		&actions[..species::feasible_prefix(input, actions)]
+	}
+	else
+	{
+		actions
+	};
+
+	if input.has_windows()
+	{
+		&actions[..schedule::timed_prefix(input, actions)]
+	}
+	else
+	{
+		actions
+	}
+}
+
+// The robot never plants without a seed, with the seeds refilled by every collect
+pub fn seed_stock_holds(input: &Input, actions: &[Action]) -> bool
+{
+	let mut seed_storage = input.seed_capacity;
+
+	for action in actions
+	{
+		match action.kind
+		{
+			ActionKind::Collect => seed_storage = input.seed_capacity,
+			ActionKind::Plant =>
+			{
+				if seed_storage == 0
+				{
+					return false;
+				}
+				seed_storage -= 1;
+			},
+		}
+	}
+
+	true
+}
+
+// The run of plants between two collects around `index` fits in the seed storage
+pub fn check_actions_at(input: &Input, actions: &[Action], index: usize) -> bool
+{
+	let mut seed = input.seed_capacity as i32;
+	for action in &actions[index..]
+	{
+		if action.kind == ActionKind::Collect
+		{
+			break;
+		}
+		seed -= 1;
+	}
+
+	for action in actions[0..index].iter().rev()
+	{
+		if action.kind == ActionKind::Collect
+		{
+			break;
+		}
+		seed -= 1;
+	}
+
+	seed >= 0
+}
+
+// Value planted after each prefix of the actions
+fn prefix_values(input: &Input, actions: &[Action]) -> Vec<u32>
+{
+	let mut values = vec![0];
+
+	for action in actions
+	{
+		let value = match action.kind
+		{
+			ActionKind::Plant => input.plant_value(action.pos),
+			ActionKind::Collect => 0,
+		};
+
+		values.push(values.last().unwrap() + value);
+	}
+
+	values
+}
+
+// Length of the prefix of the actions to keep, given the minimum distance after each reachable prefix.
+// The plan ends with a plant and has the highest value, then the lowest distance.
+fn best_end(input: &Input, actions: &[Action], distances: &[i32]) -> usize
+{
+	let values = prefix_values(input, actions);
+
+	(0..distances.len())
+		.filter(|&index| index == 0 || actions[index - 1].kind == ActionKind::Plant)
+		.max_by_key(|&index| (values[index], -distances[index], usize::MAX - index))
+		.unwrap()
+}
+
+
 pub fn solve_and_write_output(input: &Input, actions: &[Action])
 {
 	let (mut res, plant_count, distance_traveled) = resolve(input, actions);
 
-	io::write_output(res.make_contiguous(), Some(actions), plant_count, distance_traveled);
+	let value = planted_value(input, actions, plant_count);
+
+	io::write_scored_output(res.make_contiguous(), Some(actions), plant_count, value, distance_traveled);
 }
 
 
 pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize, i32)
 {
+	let actions = compatible_actions(input, actions);
+
+	if !input.obstacles.is_empty()
+	{
+		return obstacles::resolve(input, actions, true);
+	}
+
+	if input.metric != Metric::Manhattan
+	{
+		return metric::resolve(input, actions, true);
+	}
+
 	#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 	struct State
 	{
@@ -72,7 +232,7 @@ pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize
 
 	let initial_state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		action_index: 0,
 	};
 
@@ -80,6 +240,8 @@ pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize
 
 	let mut prev_move: HashMap<State, Option<BackAction>> = HashMap::new();
 
+	let values = prefix_values(input, actions);
+
 	let mut end_point: (i32, State) = (0, initial_state);
 
 	while let Some(WeightedNode(distance_traveled, (back, state))) = priority_queue.pop()
@@ -129,9 +291,16 @@ pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize
 						action_index,
 					};
 
-					if (action_index, -distance_traveled) > (end_point.1.action_index, -end_point.0)
+					let total_distance = distance_traveled + input.return_distance(pos);
+
+					if total_distance > input.max_distance as i32
+					{
+						continue;
+					}
+
+					if (values[action_index], -total_distance) > (values[end_point.1.action_index], -end_point.0)
 					{
-						end_point = (distance_traveled, new_state);
+						end_point = (total_distance, new_state);
 					}
 
 					priority_queue.push(WeightedNode(distance_traveled, (back, new_state)));
@@ -154,9 +323,16 @@ pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize
 							action_index,
 						};
 
-						if (action_index, -new_distance_traveled) > (end_point.1.action_index, -end_point.0)
+						let total_distance = new_distance_traveled + input.return_distance(new_pos);
+
+						if total_distance > input.max_distance as i32
+						{
+							continue;
+						}
+
+						if (values[action_index], -total_distance) > (values[end_point.1.action_index], -end_point.0)
 						{
-							end_point = (new_distance_traveled, new_state);
+							end_point = (total_distance, new_state);
 						}
 	
 						priority_queue.push(WeightedNode(new_distance_traveled, (back, new_state)));
@@ -168,7 +344,7 @@ pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize
 				let dist = distance(pos, action.pos);
 				let new_distance_traveled = distance_traveled + dist;
 				
-				if new_distance_traveled > input.max_distance as i32
+				if new_distance_traveled + input.return_distance(action.pos) > input.max_distance as i32
 				{
 					continue;
 				}
@@ -189,6 +365,8 @@ pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize
 	
 	let mut moves = VecDeque::new();
 
+	let end_pos = state.robot_pos;
+
 	let mut state = &state;
 
 	let mut plant_count = 0;
@@ -218,12 +396,31 @@ pub fn resolve(input: &Input, actions: &[Action]) -> (VecDeque<OutAction>, usize
 		back = &prev_move[state];
 	}
 
+	if input.return_to_base && end_pos != input.start
+	{
+		moves.push_back(OutAction::Move(input.start));
+	}
+
 	(moves, plant_count, distance_traveled)
 }
 
 
 pub fn resolve_fast(input: &Input, actions: &[Action], limit_distance: bool) -> (usize, i32)
 {
+	let actions = compatible_actions(input, actions);
+
+	if !input.obstacles.is_empty()
+	{
+		let (_, plant_count, distance_traveled) = obstacles::resolve(input, actions, limit_distance);
+		return (plant_count, distance_traveled);
+	}
+
+	if input.metric != Metric::Manhattan
+	{
+		let (_, plant_count, distance_traveled) = metric::resolve(input, actions, limit_distance);
+		return (plant_count, distance_traveled);
+	}
+
 	#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 	struct State
 	{
@@ -235,7 +432,7 @@ pub fn resolve_fast(input: &Input, actions: &[Action], limit_distance: bool) ->
 
 	let initial_state = State
 	{
-		robot_pos: [0, 0],
+		robot_pos: input.start,
 		action_index: 0,
 	};
 
@@ -243,6 +440,8 @@ pub fn resolve_fast(input: &Input, actions: &[Action], limit_distance: bool) ->
 
 	let mut explored: HashSet<State> = HashSet::new();
 
+	let values = prefix_values(input, actions);
+
 	let mut end_point: (i32, State) = (0, initial_state);
 
 	while let Some(WeightedNode(distance_traveled, state)) = priority_queue.pop()
@@ -284,9 +483,16 @@ pub fn resolve_fast(input: &Input, actions: &[Action], limit_distance: bool) ->
 						action_index,
 					};
 
-					if (action_index, -distance_traveled) > (end_point.1.action_index, -end_point.0)
+					let total_distance = distance_traveled + input.return_distance(pos);
+
+					if limit_distance && total_distance > input.max_distance as i32
+					{
+						continue;
+					}
+
+					if (values[action_index], -total_distance) > (values[end_point.1.action_index], -end_point.0)
 					{
-						end_point = (distance_traveled, new_state);
+						end_point = (total_distance, new_state);
 					}
 
 					priority_queue.push(WeightedNode(distance_traveled, new_state));
@@ -309,9 +515,16 @@ pub fn resolve_fast(input: &Input, actions: &[Action], limit_distance: bool) ->
 							action_index,
 						};
 
-						if (action_index, -new_distance_traveled) > (end_point.1.action_index, -end_point.0)
+						let total_distance = new_distance_traveled + input.return_distance(new_pos);
+
+						if limit_distance && total_distance > input.max_distance as i32
 						{
-							end_point = (new_distance_traveled, new_state);
+							continue;
+						}
+
+						if (values[action_index], -total_distance) > (values[end_point.1.action_index], -end_point.0)
+						{
+							end_point = (total_distance, new_state);
 						}
 	
 						priority_queue.push(WeightedNode(new_distance_traveled, new_state));
@@ -323,7 +536,7 @@ pub fn resolve_fast(input: &Input, actions: &[Action], limit_distance: bool) ->
 				let dist = distance(pos, action.pos);
 				let new_distance_traveled = distance_traveled + dist;
 				
-				if limit_distance && new_distance_traveled > input.max_distance as i32
+				if limit_distance && new_distance_traveled + input.return_distance(action.pos) > input.max_distance as i32
 				{
 					continue;
 				}
@@ -357,16 +570,37 @@ pub fn resolve_fast(input: &Input, actions: &[Action], limit_distance: bool) ->
 
 pub fn resolve_q_fast(input: &Input, actions: &[Action], limit_distance: bool) -> (usize, i32)
 {
-	let mut pos = QPos::default();
+	let actions = compatible_actions(input, actions);
+
+	if !input.obstacles.is_empty()
+	{
+		// The position sets are only exact with free movement
+		return resolve_fast(input, actions, limit_distance);
+	}
+
+	match input.metric
+	{
+		Metric::Manhattan => resolve_sets::<QRect>(input, actions, limit_distance),
+		_ => resolve_sets::<CellSet>(input, actions, limit_distance),
+	}
+}
+
+fn resolve_sets<P: PositionSet>(input: &Input, actions: &[Action], limit_distance: bool) -> (usize, i32)
+{
+	let mut pos = P::from_cell(input.start);
 	let mut distance_traveled = 0;
-	let mut distance_back = 0;
 	let mut plant_count = 0;
+	let mut value = 0;
+
+	// Like `resolve`, keep the prefix ending with a plant with the highest value
+	let mut best = (0, 0, 0);
 
 	for action in actions
 	{
 		let (new_pos, dist) = pos.apply_action(input, action);
+		let return_distance = new_pos.return_distance(input);
 		
-		if limit_distance && (distance_traveled + dist) as u32 > input.max_distance
+		if limit_distance && (distance_traveled + dist + return_distance) as u32 > input.max_distance
 		{
 			break;
 		}
@@ -374,30 +608,28 @@ pub fn resolve_q_fast(input: &Input, actions: &[Action], limit_distance: bool) -
 		pos = new_pos;
 		distance_traveled += dist;
 
-		match action.kind
+		if action.kind == ActionKind::Plant
 		{
-			ActionKind::Plant =>
-			{
-				plant_count += 1;
-				distance_back = 0;
-			},
-			ActionKind::Collect =>
+			plant_count += 1;
+			value += input.plant_value(action.pos);
+
+			if value > best.0
 			{
-				distance_back += dist;
-			},
+				best = (value, plant_count, distance_traveled + return_distance);
+			}
 		}
 	}
 
-	(plant_count, distance_traveled - distance_back)
+	(best.1, best.2)
 }
 
 
 
-pub fn unresolve(actions: &[OutAction]) -> Vec<Action>
+pub fn unresolve(input: &Input, actions: &[OutAction]) -> Vec<Action>
 {
 	let mut moves = Vec::new();
 
-	let mut robot_pos = [0, 0];
+	let mut robot_pos = input.start;
 
 	for action in actions
 	{
@@ -0,0 +1,161 @@
+use crate::io::Input;
+use crate::{score, seed_stock_holds, Action, ActionKind};
+
+
+const CANDIDATES: usize = 5;
+
+
+// Cell of the action and the distance it is done from, a plant is done from anywhere within the range
+pub fn reach(input: &Input, action: &Action) -> ([i32; 2], i32)
+{
+	(action.pos, if action.kind == ActionKind::Plant { input.range.max(0) } else { 0 })
+}
+
+// The route is a list of slots: the start, then the actions of `order`
+fn slot(input: &Input, actions: &[Action], order: &[usize], slot: usize) -> Option<([i32; 2], i32)>
+{
+	match slot
+	{
+		0 => Some((input.start, 0)),
+		_ => order.get(slot - 1).map(|&index| reach(input, &actions[index])),
+	}
+}
+
+// Lower bound of the distance between the reaches of two actions.
+// Past the end of the route it is the way back when the robot must return.
+pub fn cost(input: &Input, from: ([i32; 2], i32), to: Option<([i32; 2], i32)>) -> i32
+{
+	let to = match to
+	{
+		Some(to) => to,
+		None if input.return_to_base => (input.start, 0),
+		None => return 0,
+	};
+
+	(input.travel_distance(from.0, to.0) - from.1 - to.1).max(0)
+}
+
+// Gain of reversing the slots a+1..=b, from the costs of the two edges removed and the two added
+fn reversal_gain(input: &Input, actions: &[Action], order: &[usize], a: usize, b: usize) -> i32
+{
+	let slot = |index| slot(input, actions, order, index);
+	let (before, first, last, next) = (slot(a).unwrap(), slot(a + 1).unwrap(), slot(b).unwrap(), slot(b + 1));
+
+	cost(input, before, Some(first)) + cost(input, last, next) - cost(input, before, Some(last)) - cost(input, first, next)
+}
+
+// Indices of the nearest `count` actions of each action, by distance between their positions
+pub fn candidate_lists(input: &Input, actions: &[Action], count: usize) -> Vec<Vec<usize>>
+{
+	(0..actions.len())
+		.map(|index|
+		{
+			let mut nearest: Vec<(i32, usize)> = (0..actions.len())
+				.filter(|&other| other != index)
+				.map(|other| (input.travel_distance(actions[index].pos, actions[other].pos), other))
+				.collect();
+
+			let count = count.min(nearest.len());
+			if count > 0 && count < nearest.len()
+			{
+				nearest.select_nth_unstable(count - 1);
+			}
+
+			nearest.truncate(count);
+			nearest.sort_unstable();
+			nearest.into_iter().map(|(_, other)| other).collect()
+		})
+		.collect()
+}
+
+
+// Lin-Kernighan style improvement: from each action, a chain of segment reversals joining it to one of its
+// nearest actions, each time the one with the best gain, while the cumulative gain from the edge costs stays
+// positive and for at most `max_depth` reversals. The best plan of the chain is kept when it plants more or
+// travels less and no plant lacks a seed, until no chain improves the plan.
+pub fn lin_kernighan(input: &Input, actions: &[Action], max_depth: usize) -> Vec<Action>
+{
+	let candidates = candidate_lists(input, actions, CANDIDATES);
+
+	let plan = |order: &[usize]| -> Vec<Action> { order.iter().map(|&index| actions[index]).collect() };
+
+	// Actions by index in `actions`, and the slot of each one
+	let mut order: Vec<usize> = (0..actions.len()).collect();
+	let mut value = score(input, actions);
+
+	loop
+	{
+		let mut improved = false;
+
+		for anchor in 0..actions.len()
+		{
+			let mut trial = order.clone();
+			let mut slots = vec![0; actions.len()];
+			for (index, &action) in trial.iter().enumerate()
+			{
+				slots[action] = index + 1;
+			}
+
+			let mut gain = 0;
+			let mut best_trial = None;
+
+			for _depth in 0..max_depth
+			{
+				let i = slots[anchor];
+
+				// Reversal making the anchor the neighbour of a candidate, on either side. The edges added are
+				// (a, b) and (a+1, b+1), the second one reverses the actions right after the start when a is 0.
+				let best_move = candidates[anchor].iter()
+					.map(|&candidate| slots[candidate])
+					.flat_map(|j| [(i, j), (j, i), (i - 1, j - 1), (j - 1, i - 1)])
+					.filter(|&(a, b)| a + 1 < b)
+					.map(|(a, b)| (reversal_gain(input, actions, &trial, a, b), a, b))
+					.max_by_key(|&(step_gain, a, b)| (step_gain, std::cmp::Reverse((a, b))));
+
+				let Some((step_gain, a, b)) = best_move
+				else
+				{
+					break;
+				};
+
+				if gain + step_gain <= 0
+				{
+					break;
+				}
+
+				gain += step_gain;
+				trial[a..b].reverse();
+				for index in a..b
+				{
+					slots[trial[index]] = index + 1;
+				}
+
+				let trial_plan = plan(&trial);
+
+				if seed_stock_holds(input, &trial_plan)
+				{
+					let new_value = score(input, &trial_plan);
+
+					if new_value > value
+					{
+						value = new_value;
+						best_trial = Some(trial.clone());
+					}
+				}
+			}
+
+			if let Some(best_trial) = best_trial
+			{
+				order = best_trial;
+				improved = true;
+			}
+		}
+
+		if !improved
+		{
+			break;
+		}
+	}
+
+	plan(&order)
+}
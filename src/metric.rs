@@ -0,0 +1,208 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use serde::Deserialize;
+
+use crate::io::{Input, OutAction};
+use crate::{best_end, Action, ActionKind};
+
+
+// How the robot moves, a MOVE costs the distance between its two end points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Metric
+{
+	// 4 directions, the original contest
+	#[default]
+	Manhattan,
+	// 8 directions, diagonal steps cost 1
+	Chebyshev,
+	// Straight line, rounded to the nearest integer
+	Euclidean,
+}
+
+impl Metric
+{
+	pub fn distance(&self, a: [i32; 2], b: [i32; 2]) -> i32
+	{
+		let delta = [(a[0] - b[0]).abs(), (a[1] - b[1]).abs()];
+
+		match self
+		{
+			Metric::Manhattan => delta[0] + delta[1],
+			Metric::Chebyshev => i32::max(delta[0], delta[1]),
+			Metric::Euclidean =>
+			{
+				let delta = [delta[0] as f64, delta[1] as f64];
+				(delta[0] * delta[0] + delta[1] * delta[1]).sqrt().round() as i32
+			},
+		}
+	}
+
+	// Unit moves on a grid, only the metrics where paths are made of unit moves have them
+	pub fn steps(&self) -> Option<&'static [[i32; 2]]>
+	{
+		match self
+		{
+			Metric::Manhattan => Some(&[[1, 0], [-1, 0], [0, 1], [0, -1]]),
+			Metric::Chebyshev => Some(&[[1, 0], [-1, 0], [0, 1], [0, -1], [1, 1], [1, -1], [-1, 1], [-1, -1]]),
+			Metric::Euclidean => None,
+		}
+	}
+
+	// Every cell at a distance lower or equal to `radius` of `center`
+	pub fn ball(&self, center: [i32; 2], radius: i32) -> impl Iterator<Item = [i32; 2]> + '_
+	{
+		(-radius..=radius)
+			.flat_map(move |dx| (-radius..=radius).map(move |dy| [center[0] + dx, center[1] + dy]))
+			.filter(move |&pos| self.distance(pos, center) <= radius)
+	}
+
+	// Cells of the ball next to a cell outside of it, the robot never needs to go further inside to plant
+	pub fn edge(&self, center: [i32; 2], radius: i32) -> impl Iterator<Item = [i32; 2]> + '_
+	{
+		self.ball(center, radius)
+			.filter(move |&pos|
+			{
+				[[1, 0], [-1, 0], [0, 1], [0, -1]].iter()
+					.any(|delta| self.distance([pos[0] + delta[0], pos[1] + delta[1]], center) > radius)
+			})
+	}
+}
+
+
+// Set of equally good robot positions, like `QPos`. `QPos` and `QRect` are only valid for the Manhattan metric,
+// `CellSet` works with any metric.
+pub trait PositionSet: Clone + Hash + Eq
+{
+	fn from_cell(pos: [i32; 2]) -> Self;
+
+	fn apply_plant(&self, input: &Input, plant_pos: [i32; 2]) -> (Self, i32);
+
+	fn apply_seed(&self, input: &Input, seed_pos: [i32; 2]) -> (Self, i32);
+
+	fn apply_action(&self, input: &Input, action: &Action) -> (Self, i32)
+	{
+		match action.kind
+		{
+			ActionKind::Plant => self.apply_plant(input, action.pos),
+			ActionKind::Collect => self.apply_seed(input, action.pos),
+		}
+	}
+
+	// Distance to go back to the start from the closest position of the set, when the input requires it
+	fn return_distance(&self, input: &Input) -> i32
+	{
+		if input.return_to_base
+		{
+			self.apply_seed(input, input.start).1
+		}
+		else
+		{
+			0
+		}
+	}
+}
+
+
+// Robot positions reachable with the minimum distance to reach them and the position they come from
+type Layer = HashMap<[i32; 2], (i32, [i32; 2])>;
+
+fn push(layer: &mut Layer, pos: [i32; 2], dist: i32, prev_pos: [i32; 2])
+{
+	if layer.get(&pos).is_none_or(|&(old_dist, _)| dist < old_dist)
+	{
+		layer.insert(pos, (dist, prev_pos));
+	}
+}
+
+// `resolve` for any metric: the robot stays if it is in range of the plant, or moves to a cell of the edge of its range
+pub fn resolve(input: &Input, actions: &[Action], limit_distance: bool) -> (VecDeque<OutAction>, usize, i32)
+{
+	let metric = input.metric;
+
+	let max_distance = if limit_distance { input.max_distance as i32 } else { i32::MAX };
+
+	// One layer per action, the first one is the start position
+	let mut layers: Vec<Layer> = vec![[(input.start, (0, input.start))].into()];
+
+	for action in actions
+	{
+		let prev_layer = layers.last().unwrap();
+		let mut layer = Layer::new();
+
+		match action.kind
+		{
+			ActionKind::Plant =>
+			{
+				for (&pos, &(dist, _)) in prev_layer
+				{
+					if metric.distance(pos, action.pos) <= input.range
+					{
+						push(&mut layer, pos, dist, pos);
+					}
+				}
+
+				for new_pos in metric.edge(action.pos, input.range)
+				{
+					if let Some((dist, pos)) = prev_layer.iter().map(|(&pos, &(dist, _))| (dist + metric.distance(pos, new_pos), pos)).min()
+					{
+						push(&mut layer, new_pos, dist, pos);
+					}
+				}
+			},
+			ActionKind::Collect =>
+			{
+				if let Some((dist, pos)) = prev_layer.iter().map(|(&pos, &(dist, _))| (dist + metric.distance(pos, action.pos), pos)).min()
+				{
+					push(&mut layer, action.pos, dist, pos);
+				}
+			},
+		}
+
+		layer.retain(|&pos, (dist, _)| *dist + input.return_distance(pos) <= max_distance);
+
+		if layer.is_empty()
+		{
+			break;
+		}
+
+		layers.push(layer);
+	}
+
+	// Like `resolve`, the plan ends with the plant giving the highest value
+	let distances: Vec<i32> = layers.iter().map(|layer| layer.iter().map(|(&pos, &(dist, _))| dist + input.return_distance(pos)).min().unwrap()).collect();
+	let end = best_end(input, actions, &distances);
+
+	let plant_count = actions[0..end].iter().filter(|action| action.kind == ActionKind::Plant).count();
+
+	let (mut pos, distance_traveled) = layers[end].iter()
+		.map(|(pos, (dist, _))| (pos, dist + input.return_distance(*pos)))
+		.min_by_key(|&(pos, dist)| (dist, *pos))
+		.unwrap();
+
+	let end_pos = *pos;
+
+	let mut moves = VecDeque::new();
+
+	for index in (1..=end).rev()
+	{
+		let (_, prev_pos) = layers[index][pos];
+
+		moves.push_front(actions[index - 1].as_output());
+
+		if prev_pos != *pos
+		{
+			moves.push_front(OutAction::Move(*pos));
+		}
+
+		pos = layers[index - 1].get_key_value(&prev_pos).unwrap().0;
+	}
+
+	if input.return_to_base && end_pos != input.start
+	{
+		moves.push_back(OutAction::Move(input.start));
+	}
+
+	(moves, plant_count, distance_traveled)
+}
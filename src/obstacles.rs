@@ -0,0 +1,535 @@
+use std::cell::RefCell;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+
+use serde::Deserialize;
+
+use crate::dijkstra::WeightedNode;
+use crate::io::{Input, OutAction};
+use crate::metric::Metric;
+use crate::{best_end, Action, ActionKind};
+
+
+// Distance given when there is no path between two cells
+pub const UNREACHABLE: i32 = i32::MAX / 4;
+
+// Reachable cells with the minimum distance to reach them and the cell the path comes from
+type Layer = HashMap<[i32; 2], (i32, [i32; 2])>;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Obstacle
+{
+	Cell([i32; 2]),
+	Rect
+	{
+		min: [i32; 2],
+		max: [i32; 2],
+	},
+}
+
+impl Obstacle
+{
+	fn bounds(&self) -> ([i32; 2], [i32; 2])
+	{
+		match *self
+		{
+			Obstacle::Cell(pos) => (pos, pos),
+			Obstacle::Rect { min, max } => (min, max),
+		}
+	}
+}
+
+
+#[derive(Debug, Clone)]
+struct Grid<'l>
+{
+	metric: Metric,
+	steps: &'static [[i32; 2]],
+	obstacles: &'l [Obstacle],
+	// Bounding box of the obstacles grown by one cell to walk around them
+	min: [i32; 2],
+	max: [i32; 2],
+}
+
+// Shortest paths never need to leave the bounding box of the obstacles and of their end points
+#[derive(Debug, Clone, Copy)]
+struct Bounds
+{
+	min: [i32; 2],
+	max: [i32; 2],
+}
+
+impl Bounds
+{
+	fn contains(&self, pos: [i32; 2]) -> bool
+	{
+		(0..2).all(|axis| (self.min[axis]..=self.max[axis]).contains(&pos[axis]))
+	}
+}
+
+impl<'l> Grid<'l>
+{
+	fn new(input: &'l Input) -> Self
+	{
+		// Without obstacles no path is searched, any metric works
+		let steps = if input.obstacles.is_empty() { input.metric.steps().unwrap_or_default() } else { input.metric.steps().expect("Obstacles need a metric made of unit moves") };
+
+		let mut min = [i32::MAX; 2];
+		let mut max = [i32::MIN; 2];
+
+		for obstacle in &input.obstacles
+		{
+			let (obstacle_min, obstacle_max) = obstacle.bounds();
+
+			for axis in 0..2
+			{
+				min[axis] = i32::min(min[axis], obstacle_min[axis] - 1);
+				max[axis] = i32::max(max[axis], obstacle_max[axis] + 1);
+			}
+		}
+
+		Grid { metric: input.metric, steps, obstacles: &input.obstacles, min, max }
+	}
+
+	fn bounds<'p>(&self, points: impl IntoIterator<Item = &'p [i32; 2]>) -> Bounds
+	{
+		let mut bounds = Bounds { min: self.min, max: self.max };
+
+		for pos in points
+		{
+			for (axis, &coord) in pos.iter().enumerate()
+			{
+				bounds.min[axis] = i32::min(bounds.min[axis], coord);
+				bounds.max[axis] = i32::max(bounds.max[axis], coord);
+			}
+		}
+
+		bounds
+	}
+
+	fn is_blocked(&self, pos: [i32; 2]) -> bool
+	{
+		self.obstacles.iter().any(|obstacle|
+		{
+			let (min, max) = obstacle.bounds();
+			(0..2).all(|axis| (min[axis]..=max[axis]).contains(&pos[axis]))
+		})
+	}
+
+	fn neighbours(&self, pos: [i32; 2], bounds: Bounds) -> impl Iterator<Item = [i32; 2]> + '_
+	{
+		self.steps.iter()
+			.map(move |delta| [pos[0] + delta[0], pos[1] + delta[1]])
+			.filter(move |&pos| bounds.contains(pos) && !self.is_blocked(pos))
+	}
+
+	// A* with the distance without obstacles as heuristic
+	fn path(&self, from: [i32; 2], to: [i32; 2]) -> Option<Vec<[i32; 2]>>
+	{
+		let bounds = self.bounds([&from, &to]);
+
+		let mut priority_queue = BinaryHeap::new();
+		let mut prev: HashMap<[i32; 2], [i32; 2]> = HashMap::new();
+		let mut costs: HashMap<[i32; 2], i32> = HashMap::new();
+
+		costs.insert(from, 0);
+		priority_queue.push(WeightedNode(self.metric.distance(from, to), from));
+
+		while let Some(WeightedNode(_, pos)) = priority_queue.pop()
+		{
+			if pos == to
+			{
+				let mut path = vec![to];
+				while let Some(&prev_pos) = prev.get(path.last().unwrap())
+				{
+					path.push(prev_pos);
+				}
+				path.reverse();
+				return Some(path);
+			}
+
+			let cost = costs[&pos] + 1;
+
+			for new_pos in self.neighbours(pos, bounds)
+			{
+				if costs.get(&new_pos).is_some_and(|&old_cost| old_cost <= cost)
+				{
+					continue;
+				}
+
+				costs.insert(new_pos, cost);
+				prev.insert(new_pos, pos);
+				priority_queue.push(WeightedNode(cost + self.metric.distance(new_pos, to), new_pos));
+			}
+		}
+
+		None
+	}
+
+	// Multi-source Dijkstra, minimum distance to each target cell with the source it comes from
+	fn spread(&self, sources: &Layer, targets: &HashSet<[i32; 2]>, max_distance: i32) -> Layer
+	{
+		let bounds = self.bounds(sources.keys().chain(targets));
+
+		let mut priority_queue = BinaryHeap::new();
+		let mut explored: HashSet<[i32; 2]> = HashSet::new();
+		let mut res = HashMap::new();
+
+		for (&pos, &(dist, _)) in sources
+		{
+			priority_queue.push(WeightedNode(dist, (pos, pos)));
+		}
+
+		while let Some(WeightedNode(dist, (pos, source))) = priority_queue.pop()
+		{
+			if dist > max_distance || res.len() == targets.len()
+			{
+				break;
+			}
+
+			if !explored.insert(pos)
+			{
+				continue;
+			}
+
+			if targets.contains(&pos)
+			{
+				res.insert(pos, (dist, source));
+			}
+
+			for new_pos in self.neighbours(pos, bounds)
+			{
+				if !explored.contains(&new_pos)
+				{
+					priority_queue.push(WeightedNode(dist + 1, (new_pos, source)));
+				}
+			}
+		}
+
+		res
+	}
+}
+
+
+// Shortest path distances between points of interest, built by `Input::index` and shared by the clones of the input.
+// It keeps the metric and the obstacles it was built for, an input they do not match is not cached.
+pub struct PathCache
+{
+	metric: Metric,
+	obstacles: Vec<Obstacle>,
+	// Points of interest of the input when it was indexed: the start, the plants and the seed piles
+	sources: HashSet<[i32; 2]>,
+	// Box covered by the distance fields: the obstacles, and the points of interest with their range
+	bounds: Bounds,
+	// Distance from a point of interest to every cell of the box, computed on the first use
+	fields: Mutex<HashMap<[i32; 2], Vec<i32>>>,
+	// Distances between the other cells, keyed by their end points
+	distances: Mutex<HashMap<[[i32; 2]; 2], i32>>,
+}
+
+impl std::fmt::Debug for PathCache
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		f.debug_struct("PathCache")
+			.field("metric", &self.metric)
+			.field("obstacles", &self.obstacles)
+			.field("fields", &self.fields.lock().unwrap().len())
+			.finish_non_exhaustive()
+	}
+}
+
+impl PathCache
+{
+	pub fn new(input: &Input) -> Self
+	{
+		let sources: HashSet<[i32; 2]> = [input.start].into_iter().chain(input.plants.iter().copied()).chain(input.seeds.iter().copied()).collect();
+
+		let mut bounds = Grid::new(input).bounds(&sources);
+		for axis in 0..2
+		{
+			bounds.min[axis] -= input.range;
+			bounds.max[axis] += input.range;
+		}
+
+		PathCache
+		{
+			metric: input.metric,
+			obstacles: input.obstacles.clone(),
+			sources,
+			bounds,
+			fields: Mutex::default(),
+			distances: Mutex::default(),
+		}
+	}
+
+	pub fn matches(&self, input: &Input) -> bool
+	{
+		self.metric == input.metric && self.obstacles == input.obstacles
+	}
+
+	fn cell_index(&self, pos: [i32; 2]) -> usize
+	{
+		let width = (self.bounds.max[0] - self.bounds.min[0] + 1) as usize;
+		(pos[1] - self.bounds.min[1]) as usize * width + (pos[0] - self.bounds.min[0]) as usize
+	}
+
+	// Breadth first search from the source over the whole box
+	fn field(&self, grid: &Grid, source: [i32; 2]) -> Vec<i32>
+	{
+		let mut field = vec![UNREACHABLE; self.cell_index(self.bounds.max) + 1];
+		let mut queue = VecDeque::from([source]);
+
+		field[self.cell_index(source)] = 0;
+
+		while let Some(pos) = queue.pop_front()
+		{
+			let dist = field[self.cell_index(pos)] + 1;
+
+			for new_pos in grid.neighbours(pos, self.bounds)
+			{
+				let index = self.cell_index(new_pos);
+
+				if field[index] == UNREACHABLE
+				{
+					field[index] = dist;
+					queue.push_back(new_pos);
+				}
+			}
+		}
+
+		field
+	}
+
+	fn distance(&self, input: &Input, a: [i32; 2], b: [i32; 2]) -> i32
+	{
+		let grid = Grid::new(input);
+
+		// Like the search, a path may leave a blocked cell but never enter one
+		if a == b
+		{
+			return 0;
+		}
+		if grid.is_blocked(b)
+		{
+			return UNREACHABLE;
+		}
+
+		if self.bounds.contains(a) && self.bounds.contains(b)
+		{
+			let source = [a, b].into_iter().find(|pos| self.sources.contains(pos));
+
+			if let Some(source) = source
+			{
+				let mut fields = self.fields.lock().unwrap();
+				let field = fields.entry(source).or_insert_with(|| self.field(&grid, source));
+
+				if source != a && grid.is_blocked(a)
+				{
+					return grid.neighbours(a, self.bounds).map(|pos| field[self.cell_index(pos)] + 1).min().unwrap_or(UNREACHABLE).min(UNREACHABLE);
+				}
+
+				return field[self.cell_index(if source == a { b } else { a })];
+			}
+		}
+
+		// A blocked start makes the distance one way only
+		let key = [a, b];
+
+		if let Some(&dist) = self.distances.lock().unwrap().get(&key)
+		{
+			return dist;
+		}
+
+		let dist = grid.path(a, b).map_or(UNREACHABLE, |path| path.len() as i32 - 1);
+
+		self.distances.lock().unwrap().insert(key, dist);
+
+		dist
+	}
+}
+
+
+// Shortest path distance around the obstacles, `UNREACHABLE` when there is no path
+pub fn distance(input: &Input, a: [i32; 2], b: [i32; 2]) -> i32
+{
+	match input.path_cache.as_ref().filter(|cache| cache.matches(input))
+	{
+		Some(cache) => cache.distance(input, a, b),
+		None => Grid::new(input).path(a, b).map_or(UNREACHABLE, |path| path.len() as i32 - 1),
+	}
+}
+
+
+pub fn is_blocked(input: &Input, pos: [i32; 2]) -> bool
+{
+	Grid::new(input).is_blocked(pos)
+}
+
+
+// Travel distances of an input, the shortest paths around the obstacles are cached between points of interest.
+// Built once for a search and passed to it, it uses the cache of the input when there is one.
+#[derive(Debug, Clone)]
+pub struct Distances<'l>
+{
+	input: &'l Input,
+	grid: Grid<'l>,
+	// Keyed by the ordered pair of end points
+	cache: RefCell<HashMap<[[i32; 2]; 2], i32>>,
+}
+
+impl<'l> Distances<'l>
+{
+	pub fn new(input: &'l Input) -> Self
+	{
+		Distances { input, grid: Grid::new(input), cache: RefCell::default() }
+	}
+
+	pub fn is_blocked(&self, pos: [i32; 2]) -> bool
+	{
+		self.grid.is_blocked(pos)
+	}
+
+	pub fn distance(&self, a: [i32; 2], b: [i32; 2]) -> i32
+	{
+		if self.input.obstacles.is_empty()
+		{
+			return self.input.metric.distance(a, b);
+		}
+
+		// The input shares its cache with the other searches
+		if self.input.path_cache.as_ref().is_some_and(|cache| cache.matches(self.input))
+		{
+			return distance(self.input, a, b);
+		}
+
+		let key = if a < b { [a, b] } else { [b, a] };
+
+		if let Some(&dist) = self.cache.borrow().get(&key)
+		{
+			return dist;
+		}
+
+		let dist = self.grid.path(a, b).map_or(UNREACHABLE, |path| path.len() as i32 - 1);
+
+		self.cache.borrow_mut().insert(key, dist);
+
+		dist
+	}
+
+	pub fn return_distance(&self, pos: [i32; 2]) -> i32
+	{
+		if self.input.return_to_base
+		{
+			self.distance(pos, self.input.start)
+		}
+		else
+		{
+			0
+		}
+	}
+
+	// Shortest path as a list of straight moves, excluding the start
+	pub fn waypoints(&self, a: [i32; 2], b: [i32; 2]) -> Vec<[i32; 2]>
+	{
+		if self.input.obstacles.is_empty()
+		{
+			return vec![b];
+		}
+
+		let path = self.grid.path(a, b).unwrap();
+
+		let mut waypoints = Vec::new();
+
+		for index in 1..path.len()
+		{
+			let is_turn = index + 1 < path.len() && {
+				let delta = [path[index][0] - path[index-1][0], path[index][1] - path[index-1][1]];
+				let next_delta = [path[index+1][0] - path[index][0], path[index+1][1] - path[index][1]];
+				delta != next_delta
+			};
+
+			if is_turn || index + 1 == path.len()
+			{
+				waypoints.push(path[index]);
+			}
+		}
+
+		waypoints
+	}
+}
+
+
+// `resolve` on a grid with obstacles: the robot may stand on any free cell in range of a plant
+pub fn resolve(input: &Input, actions: &[Action], limit_distance: bool) -> (VecDeque<OutAction>, usize, i32)
+{
+	let oracle = Distances::new(input);
+	let grid = &oracle.grid;
+
+	let max_distance = if limit_distance { input.max_distance as i32 } else { i32::MAX };
+
+	// One layer per action, the first one is the start position
+	let mut layers: Vec<Layer> = vec![[(input.start, (0, input.start))].into()];
+
+	for action in actions
+	{
+		let targets: HashSet<[i32; 2]> = match action.kind
+		{
+			ActionKind::Plant => input.metric.ball(action.pos, input.range)
+				.filter(|&pos| !grid.is_blocked(pos))
+				.collect(),
+			ActionKind::Collect => [action.pos].into(),
+		};
+
+		let mut layer = grid.spread(layers.last().unwrap(), &targets, max_distance);
+
+		layer.retain(|&pos, (dist, _)| *dist + oracle.return_distance(pos) <= max_distance);
+
+		if layer.is_empty()
+		{
+			break;
+		}
+
+		layers.push(layer);
+	}
+
+	let distances: Vec<i32> = layers.iter().map(|layer| layer.iter().map(|(&pos, &(dist, _))| dist + oracle.return_distance(pos)).min().unwrap()).collect();
+	let end = best_end(input, actions, &distances);
+
+	let plant_count = actions[0..end].iter().filter(|action| action.kind == ActionKind::Plant).count();
+
+	let (mut pos, distance_traveled) = layers[end].iter()
+		.map(|(pos, (dist, _))| (pos, dist + oracle.return_distance(*pos)))
+		.min_by_key(|&(pos, dist)| (dist, *pos))
+		.unwrap();
+
+	let end_pos = *pos;
+
+	let mut moves = VecDeque::new();
+
+	for index in (1..=end).rev()
+	{
+		let (_, prev_pos) = layers[index][pos];
+
+		moves.push_front(actions[index - 1].as_output());
+
+		if prev_pos != *pos
+		{
+			for waypoint in oracle.waypoints(prev_pos, *pos).into_iter().rev()
+			{
+				moves.push_front(OutAction::Move(waypoint));
+			}
+		}
+
+		pos = layers[index - 1].get_key_value(&prev_pos).unwrap().0;
+	}
+
+	if input.return_to_base && end_pos != input.start
+	{
+		moves.extend(oracle.waypoints(end_pos, input.start).into_iter().map(OutAction::Move));
+	}
+
+	(moves, plant_count, distance_traveled)
+}
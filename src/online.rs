@@ -0,0 +1,426 @@
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+use hashbrown::{Equivalent, HashMap};
+use serde::Deserialize;
+
+use crate::cell_set::CellSet;
+use crate::io::Input;
+use crate::metric::PositionSet;
+use crate::quantum::QPos;
+use crate::{Action, ActionKind};
+
+
+// Plants revealed once the robot has traveled `after` cells
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Batch
+{
+	pub after: i32,
+	pub plants: Vec<[i32; 2]>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Scenario
+{
+	pub batches: Vec<Batch>,
+}
+
+impl Scenario
+{
+	// Input with every plant of the scenario, to score the plan once the simulation is over
+	pub fn full_input(&self, input: &Input) -> Input
+	{
+		let mut full_input = input.clone();
+		full_input.plants.extend(self.batches.iter().flat_map(|batch| batch.plants.iter().copied()));
+		full_input.index();
+		full_input
+	}
+}
+
+// What the robot knows when it chooses its next action
+#[derive(Debug, Clone)]
+pub struct Observation<'l, P = QPos>
+{
+	pub robot_pos: P,
+	pub seed_storage: u32,
+	pub seeds: &'l [[i32; 2]],
+	pub plants: &'l [[i32; 2]],
+	pub distance_traveled: i32,
+}
+
+pub trait Policy<P = QPos>
+{
+	// The next action to commit to, `None` to stop
+	fn next_action(&mut self, input: &Input, observation: &Observation<P>) -> Option<Action>;
+}
+
+
+// Run the policy while the plants of the scenario are revealed, the robot only plants the plants it has seen.
+// The plants of the input are visible from the start. The robot positions are tracked with `P`, `QPos` is only
+// exact for the Manhattan metric without obstacles.
+pub fn simulate<P: PositionSet, O: Policy<P>>(input: &Input, scenario: &Scenario, policy: &mut O) -> Vec<Action>
+{
+	let mut batches = scenario.batches.clone();
+	batches.sort_by_key(|batch| batch.after);
+	let mut batches = batches.into_iter().peekable();
+
+	let mut robot_pos = P::from_cell(input.start);
+	let mut seed_storage = input.seed_capacity;
+	let mut seeds = input.seeds.clone();
+	let mut plants = input.plants.clone();
+	let mut distance_traveled = 0;
+
+	let mut actions = Vec::new();
+
+	loop
+	{
+		while let Some(batch) = batches.next_if(|batch| batch.after <= distance_traveled)
+		{
+			plants.extend(batch.plants);
+		}
+
+		let observation = Observation { robot_pos: robot_pos.clone(), seed_storage, seeds: &seeds, plants: &plants, distance_traveled };
+
+		let Some(action) = policy.next_action(input, &observation)
+		else
+		{
+			break;
+		};
+
+		match action.kind
+		{
+			ActionKind::Plant =>
+			{
+				let index = plants.iter().position(|&plant| plant == action.pos).expect("The policy planted a plant it has not seen");
+				assert!(seed_storage > 0, "The policy planted without seeds");
+				seed_storage -= 1;
+				plants.remove(index);
+			},
+			ActionKind::Collect =>
+			{
+				let index = seeds.iter().position(|&seed| seed == action.pos).expect("The policy collected an unknown seed");
+				seed_storage = input.seed_capacity;
+				seeds.remove(index);
+			},
+		}
+
+		let (new_pos, dist) = robot_pos.apply_action(input, &action);
+		robot_pos = new_pos;
+		distance_traveled += dist;
+
+		actions.push(action);
+	}
+
+	actions
+}
+
+
+// The lookahead search of pruning_time_limit, deepened while the time budget of the action lasts
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct State<P>
+{
+	robot_pos: P,
+	seed_storage: u32,
+	seeds: Vec<[i32;2]>,
+	plants: Vec<[i32;2]>,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Res
+{
+	Solved,
+	SolutionFound
+	{
+		cost: i32,
+		action: Action,
+	},
+	NoSolution,
+}
+
+#[derive(Debug, Hash)]
+struct StateAndDepth<'l, P>
+{
+	state: &'l State<P>,
+	depth: u32,
+}
+
+impl<P: PositionSet> Equivalent<(State<P>,u32)> for StateAndDepth<'_, P>
+{
+	fn equivalent(&self, key: &(State<P>,u32)) -> bool
+	{
+		self.state == &key.0 && self.depth == key.1
+	}
+}
+
+fn find_best_action_time_limit<P: PositionSet>(input: &Input, memo: &mut HashMap<(State<P>, u32), (i32, Res)>, state: &mut State<P>, max_cost: i32, time_limit: Duration) -> Res
+{
+	let start = Instant::now();
+
+	let mut last_res = None;
+
+	// A search deeper than the plants left finds the same action
+	for i in 1..=state.plants.len().max(1) as u32
+	{
+		let res = find_best_action(input, memo, state, max_cost, start, time_limit, i, i == 1);
+		if let Some(res) = res
+		{
+			last_res = Some(res);
+		}
+		else
+		{
+			break;
+		}
+	}
+
+	last_res.unwrap()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn find_best_action<P: PositionSet>(input: &Input, memo: &mut HashMap<(State<P>, u32), (i32, Res)>, state: &mut State<P>, max_cost: i32, start: Instant, time_limit: Duration, depth: u32, force_compute: bool) -> Option<Res>
+{
+	if state.plants.is_empty()
+	{
+		return Some(Res::Solved);
+	}
+
+	if depth == 0
+	{
+		return Some(Res::Solved);
+	}
+
+	if let Some((ref_max_cost, res)) = memo.get(&StateAndDepth { state, depth })
+	{
+		match ref_max_cost.cmp(&max_cost)
+		{
+			Ordering::Equal => return Some(*res),
+			Ordering::Greater =>
+			{
+				return Some(if let Res::SolutionFound { cost, .. } = res
+				{
+					if *cost < max_cost
+					{
+						*res
+					}
+					else
+					{
+						Res::NoSolution
+					}
+				}
+				else
+				{
+					*res
+				});
+			},
+			Ordering::Less =>
+			{
+				if let Res::SolutionFound { .. } = res
+				{
+					return Some(*res);
+				}
+			}
+		}
+	}
+
+
+	let pos = state.robot_pos.clone();
+
+	let mut min_cost = max_cost;
+	let mut min_action = None;
+
+	if state.seed_storage > 0
+	{
+		state.seed_storage -= 1;
+		
+		let mut plants: Vec<usize> = (0..state.plants.len()).collect();
+		plants.sort_unstable_by_key(|&index|
+		{
+			let plant = state.plants[index];
+			let (_new_pos, dist) = pos.apply_plant(input, plant);
+			dist
+		});
+
+		for index in plants
+		{
+			if !force_compute && start.elapsed() >= time_limit
+			{
+				state.robot_pos = pos.clone();
+				state.seed_storage += 1;
+				return None;
+			}
+
+			let plant = state.plants[index];
+			let (new_pos, dist) = pos.apply_plant(input, plant);
+			
+			let mut cost = dist;
+			
+			if cost >= min_cost // we are worst even without checking the children nodes, prune this branch
+			{
+				break; // Since I sorted the list, I can break here
+			}
+
+			state.robot_pos = new_pos;
+			state.plants.remove(index);
+
+			let res = find_best_action(input, memo, state, min_cost - cost, start, time_limit, depth-1, force_compute);
+
+			state.plants.insert(index, plant);
+
+			match res
+			{
+				None =>
+				{
+					state.robot_pos = pos.clone();
+					state.seed_storage += 1;
+					return None;
+				},
+				Some(Res::SolutionFound { cost: child_cost, .. }) =>
+				{
+					cost += child_cost;
+				},
+				Some(Res::Solved) =>
+				{ },
+				Some(Res::NoSolution) => continue,
+			}
+
+			if cost < min_cost
+			{
+				min_cost = cost;
+				min_action = Some(Action { pos: plant, kind: ActionKind::Plant });
+			}
+		}
+		state.robot_pos = pos.clone();
+		state.seed_storage += 1;
+	}
+
+	if state.seed_storage < input.seed_capacity
+	{
+		let mut seeds: Vec<usize> = (0..state.seeds.len()).collect();
+		seeds.sort_unstable_by_key(|&index|
+		{
+			let seed = state.seeds[index];
+			let (_new_pos, dist) = pos.apply_seed(input, seed);
+			dist
+		});
+
+		for index in seeds
+		{
+			if !force_compute && start.elapsed() >= time_limit
+			{
+				return None;
+			}
+
+			let seed = state.seeds[index];
+			let (new_pos, dist) = pos.apply_seed(input, seed);
+
+			let mut cost = dist;
+
+			if cost >= min_cost // we are worst even without checking the children nodes, prune this branch
+			{
+				break;
+			}
+
+			let  old_seed_storage = state.seed_storage;
+			state.seed_storage = input.seed_capacity;
+			state.robot_pos = new_pos;
+			state.seeds.remove(index);
+
+			let res = find_best_action(input, memo, state, min_cost - cost, start, time_limit, depth, force_compute); // collecting a seed doesn't increase the depth
+			
+			state.seed_storage = old_seed_storage;
+			state.robot_pos = pos.clone();
+			state.seeds.insert(index, seed);
+
+			match res?
+			{
+				Res::SolutionFound { cost: child_cost, .. } =>
+				{
+					cost += child_cost;
+				},
+				Res::Solved =>
+				{ },
+				Res::NoSolution => continue,
+			}
+
+			if cost < min_cost
+			{
+				min_cost = cost;
+				min_action = Some(Action { pos: seed, kind: ActionKind::Collect });
+			}
+		}
+	}
+
+	let res = if let Some(min_action) = min_action
+	{
+		Res::SolutionFound
+		{
+			cost: min_cost,
+			action: min_action,
+		}
+	}
+	else
+	{
+		Res::NoSolution
+	};
+
+	memo.insert((state.clone(), depth), (max_cost, res));
+
+	Some(res)
+}
+
+
+// Plans the next action with an iterative deepening search limited to `time_per_action`.
+// When no action fits in the distance budget, it keeps planting without the budget like pruning_time_limit.
+pub struct RollingHorizon<P = QPos>
+{
+	time_per_action: Duration,
+	limit_distance: bool,
+	memo: HashMap<(State<P>, u32), (i32, Res)>,
+}
+
+impl<P> RollingHorizon<P>
+{
+	pub fn new(time_per_action: Duration) -> Self
+	{
+		RollingHorizon { time_per_action, limit_distance: true, memo: HashMap::new() }
+	}
+}
+
+impl<P: PositionSet> Policy<P> for RollingHorizon<P>
+{
+	fn next_action(&mut self, input: &Input, observation: &Observation<P>) -> Option<Action>
+	{
+		let mut state = State
+		{
+			robot_pos: observation.robot_pos.clone(),
+			seed_storage: observation.seed_storage,
+			seeds: observation.seeds.to_vec(),
+			plants: observation.plants.to_vec(),
+		};
+
+		loop
+		{
+			let max_dist = if self.limit_distance { input.max_distance as i32 - observation.distance_traveled + 1 } else { i32::MAX };
+
+			match find_best_action_time_limit(input, &mut self.memo, &mut state, max_dist, self.time_per_action)
+			{
+				Res::SolutionFound { action, .. } => return Some(action),
+				_ if self.limit_distance => self.limit_distance = false,
+				_ => return None,
+			}
+		}
+	}
+}
+
+
+// `simulate` with the rolling horizon policy and the position set matching the metric and the obstacles
+pub fn rolling_horizon(input: &Input, scenario: &Scenario, time_per_action: Duration) -> Vec<Action>
+{
+	if input.exact_qpos()
+	{
+		simulate::<QPos, _>(input, scenario, &mut RollingHorizon::new(time_per_action))
+	}
+	else
+	{
+		simulate::<CellSet, _>(input, scenario, &mut RollingHorizon::new(time_per_action))
+	}
+}
@@ -0,0 +1,31 @@
+use crate::io::Input;
+
+
+// Seeds left in each pile at the start. Without pile sizes, piles never run out.
+pub fn pile_sizes(input: &Input) -> Vec<u32>
+{
+	if input.has_piles()
+	{
+		input.pile_sizes.clone()
+	}
+	else
+	{
+		vec![input.seed_capacity; input.seeds.len()]
+	}
+}
+
+// Seeds taken from a pile with `left` seeds and the seeds it keeps, when `needed` plants left accept its species.
+// Without pile sizes the robot fills up as before. With them, the robot takes what it can still plant and
+// leaves the rest for a later visit.
+pub fn pickup(input: &Input, carried: u32, left: u32, needed: u32) -> (u32, u32)
+{
+	let free = input.seed_capacity.saturating_sub(carried);
+
+	if !input.has_piles()
+	{
+		return (free, left);
+	}
+
+	let count = free.min(left).min(needed);
+	(count, left - count)
+}
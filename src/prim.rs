@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use crate::io::Input;
+use crate::metric::PositionSet;
 use crate::quantum::QPos;
 
 
@@ -72,12 +73,16 @@ pub fn prim(input: &Input, pos: QPos, plants: &[[i32;2]]) -> i32
 
 
 // prim's algorithm
-pub fn prim2(input: &Input, pos: QPos, plants: &[[i32;2]]) -> i32
+pub fn prim2<P: PositionSet>(input: &Input, pos: P, plants: &[[i32;2]]) -> i32
 {
-	if plants.is_empty()
-	{
-		return 0;
-	}
+	prim_costs(input, pos, plants).iter().sum()
+}
+
+// Distance paid to connect each plant to the tree of prim2, a plant with a high cost for its value is
+// worth dropping when not every plant can be planted
+pub fn prim_costs<P: PositionSet>(input: &Input, pos: P, plants: &[[i32;2]]) -> Vec<i32>
+{
+	let mut costs = vec![0; plants.len()];
 
 	let mut graph: Vec<(usize, usize, i32)> = (0..plants.len())
 		.map(|index|
@@ -88,32 +93,31 @@ pub fn prim2(input: &Input, pos: QPos, plants: &[[i32;2]]) -> i32
 		.collect();
 	let mut tree = vec![pos];
 
-	let mut tree_dist = 0;
-
 	for _i in 0..plants.len()
 	{
-		let (best_graph_index, (best_plant_index, best_tree_index, _dist)) = graph.iter().enumerate().min_by_key(|(_, (_, _, dist))| *dist).unwrap();
+		let (best_graph_index, &(best_plant_index, best_tree_index, _dist)) = graph.iter().enumerate().min_by_key(|(_, (_, _, dist))| *dist).unwrap();
 
-		let (new_pos, new_dist) = tree[*best_tree_index].apply_plant(input, plants[*best_plant_index]);
+		let (new_pos, new_dist) = tree[best_tree_index].apply_plant(input, plants[best_plant_index]);
 
 		graph.swap_remove(best_graph_index);
 
-		tree_dist += new_dist;
-		tree.push(new_pos);
+		costs[best_plant_index] = new_dist;
 
 		for (plant_index, tree_index, dist) in graph.iter_mut()
 		{
 			let (_, new_dist) = new_pos.apply_plant(input, plants[*plant_index]);
 			if new_dist < *dist
 			{
-				*tree_index = tree.len()-1;
+				*tree_index = tree.len();
 				*dist = new_dist;
 			}
 		}
+
+		tree.push(new_pos);
 	}
 
 	assert!(graph.is_empty());
 	assert_eq!(tree.len(), plants.len()+1);
 
-	tree_dist
+	costs
 }
\ No newline at end of file
@@ -0,0 +1,343 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::cell_set::CellSet;
+use crate::io::Input;
+use crate::metric::PositionSet;
+use crate::prim::{prim2, prim_costs};
+use crate::quantum::QPos;
+use crate::{Action, ActionKind};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct State<P>
+{
+	robot_pos: P,
+	seed_storage: u32,
+	seeds: Vec<[i32;2]>,
+	plants: Vec<[i32;2]>,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct MyAction
+{
+	index: usize,
+	action: Action,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Res
+{
+	Solved,
+	SolutionFound
+	{
+		cost: i32,
+		action: MyAction,
+	},
+	NoSolution,
+}
+
+fn find_best_action<P: PositionSet>(input: &Input, memo: &mut HashMap<State<P>, (i32, Res)>, state: &mut State<P>, max_cost: i32) -> Res
+{
+	if state.plants.is_empty()
+	{
+		return Res::Solved;
+	}
+
+	if let Some((ref_max_cost, res)) = memo.get(state)
+	{
+		match ref_max_cost.cmp(&max_cost)
+		{
+			Ordering::Equal => return *res,
+			Ordering::Greater =>
+			{
+				return if let Res::SolutionFound { cost, .. } = res
+				{
+					if *cost < max_cost
+					{
+						*res
+					}
+					else
+					{
+						Res::NoSolution
+					}
+				}
+				else
+				{
+					*res
+				};
+			},
+			Ordering::Less =>
+			{
+				if let Res::SolutionFound { .. } = res
+				{
+					return *res;
+				}
+			}
+		}
+	}
+
+	let pos = state.robot_pos.clone();
+
+	let mut min_cost = max_cost;
+	let mut min_action = None;
+
+	if state.seed_storage > 0
+	{
+		state.seed_storage -= 1;
+
+		let mut plants: Vec<(usize, i32)> = (0..state.plants.len())
+			.filter_map(|index|
+			{
+				let plant = state.plants[index];
+				let (new_pos, dist) = pos.apply_plant(input, plant);
+
+				if dist >= min_cost
+				{
+					return None;
+				}
+
+				state.plants.remove(index);
+				let prim = prim2(input, new_pos, &state.plants) + dist;
+				state.plants.insert(index, plant);
+
+				if prim >= min_cost
+				{
+					return None;
+				}
+
+				Some((index, prim))
+			})
+			.collect();
+
+		plants.sort_unstable_by_key(|(_index, prim)|
+		{
+			-*prim
+		});
+
+		for (index, prim) in plants
+		{
+			let plant = state.plants[index];
+			let (new_pos, dist) = pos.apply_plant(input, plant);
+			
+			let mut cost = dist;
+			
+			if prim >= min_cost // we are worst even without checking the children nodes, prune this branch
+			{
+				continue;
+			}
+
+			state.robot_pos = new_pos;
+			state.plants.remove(index);
+
+			let res = find_best_action(input, memo, state, min_cost - cost);
+			
+			state.plants.insert(index, plant);
+
+			match res
+			{
+				Res::SolutionFound { cost: child_cost, .. } =>
+				{
+					cost += child_cost;
+				},
+				Res::Solved =>
+				{
+					cost += state.robot_pos.return_distance(input);
+				},
+				Res::NoSolution => continue,
+			}
+
+			if cost < min_cost
+			{
+				min_cost = cost;
+				min_action = Some(MyAction { index, action: Action { pos: plant, kind: ActionKind::Plant } });
+			}
+		}
+		state.robot_pos = pos.clone();
+		state.seed_storage += 1;
+	}
+
+	if state.seed_storage < input.seed_capacity
+	{
+		let mut seeds: Vec<(usize, i32)> = (0..state.seeds.len())
+			.filter_map(|index|
+			{
+				let seed = state.seeds[index];
+				let (new_pos, dist) = pos.apply_seed(input, seed);
+
+				if dist >= min_cost
+				{
+					return None;
+				}
+
+				let prim = prim2(input, new_pos, &state.plants) + dist;
+
+				if prim >= min_cost
+				{
+					return None;
+				}
+
+				Some((index, prim))
+			})
+			.collect();
+		
+		seeds.sort_unstable_by_key(|(_index, prim)|
+		{
+			-*prim
+		});
+
+		for (index, prim) in seeds
+		{
+			let seed = state.seeds[index];
+			let (new_pos, dist) = pos.apply_seed(input, seed);
+
+			let mut cost = dist;
+
+			if prim >= min_cost // we are worst even without checking the children nodes, prune this branch
+			{
+				continue;
+			}
+
+			let  old_seed_storage = state.seed_storage;
+			state.seed_storage = input.seed_capacity;
+			state.robot_pos = new_pos;
+			state.seeds.remove(index);
+
+			let res = find_best_action(input, memo, state, min_cost - cost);
+			
+			state.seed_storage = old_seed_storage;
+			state.robot_pos = pos.clone();
+			state.seeds.insert(index, seed);
+
+			match res
+			{
+				Res::SolutionFound { cost: child_cost, .. } =>
+				{
+					cost += child_cost;
+				},
+				Res::Solved =>
+				{ },
+				Res::NoSolution => continue,
+			}
+
+			if cost < min_cost
+			{
+				min_cost = cost;
+				min_action = Some(MyAction { index, action: Action { pos: seed, kind: ActionKind::Collect } });
+			}
+		}
+	}
+
+	let res = if let Some(min_action) = min_action
+	{
+		Res::SolutionFound
+		{
+			cost: min_cost,
+			action: min_action,
+		}
+	}
+	else
+	{
+		Res::NoSolution
+	};
+
+	memo.insert(state.clone(), (max_cost, res));
+
+	res
+}
+
+
+// Branch and bound that plants every plant with a total distance lower or equal to `max_distance`.
+// With weighted plants, the plants that cost the most for their value are dropped until the others fit in the distance.
+pub fn solve(input: &Input, max_distance: i32) -> Vec<Action>
+{
+	if input.exact_qpos()
+	{
+		prize_collecting::<QPos>(input, max_distance)
+	}
+	else
+	{
+		prize_collecting::<CellSet>(input, max_distance)
+	}
+}
+
+fn prize_collecting<P: PositionSet>(input: &Input, max_distance: i32) -> Vec<Action>
+{
+	let mut memo = HashMap::new();
+
+	if !input.is_weighted()
+	{
+		return search::<P>(input, &mut memo, max_distance);
+	}
+
+	let mut input = input.clone();
+
+	loop
+	{
+		// The states do not depend on the plants dropped, each search goes on from the memo of the previous one
+		let actions = search::<P>(&input, &mut memo, max_distance);
+
+		if actions.iter().filter(|action| action.kind == ActionKind::Plant).count() == input.plants.len()
+		{
+			return actions;
+		}
+
+		// Connection cost in the spanning tree of each plant
+		let costs = prim_costs(&input, P::from_cell(input.start), &input.plants);
+
+		// The plant with the highest cost per unit of value is dropped, compared without division
+		let worst = (0..input.plants.len())
+			.max_by(|&a, &b| (costs[a] as i64 * input.values[b] as i64).cmp(&(costs[b] as i64 * input.values[a] as i64)))
+			.unwrap();
+
+		input.plants.remove(worst);
+		input.values.remove(worst);
+		input.index();
+	}
+}
+
+fn search<P: PositionSet>(input: &Input, memo: &mut HashMap<State<P>, (i32, Res)>, max_distance: i32) -> Vec<Action>
+{
+	let mut state = State
+	{
+		robot_pos: P::from_cell(input.start),
+		seed_storage: input.seed_capacity,
+		seeds: input.seeds.clone(),
+		plants: input.plants.clone(),
+	};
+
+	let mut distance_traveled = 0;
+
+	let mut moves = Vec::new();
+
+	while !state.plants.is_empty()
+	{
+		let Res::SolutionFound { action, .. } = find_best_action(input, memo, &mut state, max_distance - distance_traveled+1)
+		else
+		{
+			break;
+		};
+
+		let (new_pos, dist) = state.robot_pos.apply_action(input, &action.action);
+
+		distance_traveled += dist;
+		state.robot_pos = new_pos;
+
+		moves.push(action.action);
+
+		match action.action.kind
+		{
+			ActionKind::Plant =>
+			{
+				state.seed_storage -= 1;
+				state.plants.remove(action.index);
+			},
+			ActionKind::Collect =>
+			{
+				state.seed_storage = input.seed_capacity;
+				state.seeds.remove(action.index);
+			},
+		}
+	}
+
+	moves
+}
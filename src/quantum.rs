@@ -1,5 +1,6 @@
 use crate::{distance, Action, ActionKind};
 use crate::io::Input;
+use crate::metric::PositionSet;
 
 
 #[derive(Default, Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -201,3 +202,208 @@ impl QPos
 		}
 	}
 }
+
+
+// Set of equally good robot positions as a rectangle in the rotated frame [x+y, x-y].
+// Only the cells whose rotated coordinates have the same parity are part of the set.
+// The bounds are normalized so that equal sets have equal representations.
+#[derive(Default, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct QRect
+{
+	pub min: [i32; 2],
+	pub max: [i32; 2],
+}
+
+
+fn rotate(pos: [i32; 2]) -> [i32; 2]
+{
+	[pos[0] + pos[1], pos[0] - pos[1]]
+}
+
+
+impl QRect
+{
+	pub fn new(min: [i32; 2], max: [i32; 2]) -> Self
+	{
+		QRect { min, max }.normalize()
+	}
+
+	pub fn from_cell(pos: [i32; 2]) -> Self
+	{
+		let pos = rotate(pos);
+
+		QRect
+		{
+			min: pos,
+			max: pos,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.min[0] > self.max[0] || self.min[1] > self.max[1]
+	}
+
+	// When one axis is a single value, the other axis must only keep the values with the same parity
+	fn normalize(mut self) -> Self
+	{
+		for _ in 0..2
+		{
+			for axis in 0..2
+			{
+				let other = 1 - axis;
+				if self.min[axis] == self.max[axis]
+				{
+					let value = self.min[axis];
+					self.min[other] += (self.min[other] - value).rem_euclid(2);
+					self.max[other] -= (self.max[other] - value).rem_euclid(2);
+				}
+			}
+		}
+
+		self
+	}
+
+	fn intersect(&self, other: &Self) -> Self
+	{
+		QRect
+		{
+			min: [i32::max(self.min[0], other.min[0]), i32::max(self.min[1], other.min[1])],
+			max: [i32::min(self.max[0], other.max[0]), i32::min(self.max[1], other.max[1])],
+		}
+	}
+
+	// Every cell at a distance lower or equal to `dist` from the set
+	fn expand(&self, dist: i32) -> Self
+	{
+		QRect
+		{
+			min: [self.min[0] - dist, self.min[1] - dist],
+			max: [self.max[0] + dist, self.max[1] + dist],
+		}
+	}
+
+	pub fn distance(&self, pos: [i32; 2]) -> i32
+	{
+		let pos = rotate(pos);
+
+		// The closest cell is next to the clamped position, one step may be needed to fix the parity
+		let candidates = |axis: usize|
+		{
+			let value = pos[axis].clamp(self.min[axis], self.max[axis]);
+			(value-1..=value+1).filter(move |value| (self.min[axis]..=self.max[axis]).contains(value))
+		};
+
+		candidates(0)
+			.flat_map(|u| candidates(1).map(move |v| (u, v)))
+			.filter(|(u, v)| (u - v).rem_euclid(2) == 0)
+			.map(|(u, v)| i32::max((pos[0] - u).abs(), (pos[1] - v).abs()))
+			.min()
+			.unwrap()
+	}
+
+	pub fn cells(&self) -> impl Iterator<Item = [i32; 2]> + '_
+	{
+		(self.min[0]..=self.max[0])
+			.flat_map(|u| (self.min[1]..=self.max[1]).map(move |v| (u, v)))
+			.filter(|(u, v)| (u - v).rem_euclid(2) == 0)
+			.map(|(u, v)| [(u + v) / 2, (u - v) / 2])
+	}
+
+	pub fn apply_plant(&self, input: &Input, plant_pos: [i32; 2]) -> (Self, i32)
+	{
+		let dist = (self.distance(plant_pos) - input.range).max(0);
+
+		// The new positions are in range of the plant and at exactly `dist` of the old ones
+		let new_state = self.expand(dist)
+			.intersect(&QRect::from_cell(plant_pos).expand(input.range))
+			.normalize();
+
+		debug_assert!(!new_state.is_empty());
+
+		(new_state, dist)
+	}
+
+	pub fn apply_seed(&self, seed_pos: [i32; 2]) -> (Self, i32)
+	{
+		(QRect::from_cell(seed_pos), self.distance(seed_pos))
+	}
+
+	pub fn apply_action(&self, input: &Input, action: &Action) -> (Self, i32)
+	{
+		match action.kind
+		{
+			ActionKind::Plant => self.apply_plant(input, action.pos),
+			ActionKind::Collect => self.apply_seed(action.pos),
+		}
+	}
+}
+
+
+impl PositionSet for QPos
+{
+	fn from_cell(pos: [i32; 2]) -> Self
+	{
+		QPos
+		{
+			robot_pos: pos,
+			range: 0,
+		}
+	}
+
+	fn apply_plant(&self, input: &Input, plant_pos: [i32; 2]) -> (Self, i32)
+	{
+		QPos::apply_plant(self, input, plant_pos)
+	}
+
+	fn apply_seed(&self, _input: &Input, seed_pos: [i32; 2]) -> (Self, i32)
+	{
+		QPos::apply_seed(self, seed_pos)
+	}
+}
+
+impl PositionSet for QRect
+{
+	fn from_cell(pos: [i32; 2]) -> Self
+	{
+		QRect::from_cell(pos)
+	}
+
+	fn apply_plant(&self, input: &Input, plant_pos: [i32; 2]) -> (Self, i32)
+	{
+		QRect::apply_plant(self, input, plant_pos)
+	}
+
+	fn apply_seed(&self, _input: &Input, seed_pos: [i32; 2]) -> (Self, i32)
+	{
+		QRect::apply_seed(self, seed_pos)
+	}
+}
+
+
+impl From<QPos> for QRect
+{
+	fn from(pos: QPos) -> Self
+	{
+		let cell = rotate(pos.robot_pos);
+		let length = 2 * pos.range.abs();
+
+		// Up right segments go along the first rotated axis, up left ones along the second
+		if pos.range >= 0
+		{
+			QRect
+			{
+				min: cell,
+				max: [cell[0] + length, cell[1]],
+			}
+		}
+		else
+		{
+			QRect
+			{
+				min: [cell[0], cell[1] - length],
+				max: cell,
+			}
+		}
+	}
+}
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use crate::io::{Input, OutAction};
+use crate::obstacles::{Distances, UNREACHABLE};
+use crate::{best_end, compatible_actions, planted_value, Action, ActionKind};
+
+
+// Robot positions reachable with the minimum distance to reach them, one layer per action like in `resolve`
+type Layer = HashMap<[i32; 2], i32>;
+
+
+#[derive(Debug, Clone)]
+pub struct Realization
+{
+	pub plant_count: usize,
+	pub value: u32,
+	pub distance_traveled: i32,
+	// Actions kept by the resolution, the trailing collects and the actions over budget are removed
+	pub actions: Vec<Action>,
+	// For each kept action, every robot position used by at least one optimal realization,
+	// with the distance traveled when doing the action from there, without the return leg
+	pub positions: Vec<Vec<([i32; 2], i32)>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TieBreak
+{
+	// Smallest position first
+	Lexicographic,
+	// Position closest to the next seed to collect
+	NextSeed,
+}
+
+
+// Positions where the robot can do the action from `pos`, with the distance to reach them
+fn successors(input: &Input, distances: &Distances, pos: [i32; 2], action: &Action) -> Vec<([i32; 2], i32)>
+{
+	match action.kind
+	{
+		ActionKind::Plant if input.exact_qpos() =>
+		{
+			let delta = [action.pos[0] - pos[0], action.pos[1] - pos[1]];
+			let abs = [delta[0].abs(), delta[1].abs()];
+			let dist = abs[0] + abs[1];
+
+			if dist <= input.range
+			{
+				// No move required
+				return vec![(pos, 0)];
+			}
+
+			let sign = [delta[0].signum(), delta[1].signum()];
+			(i32::max(0, input.range - abs[1])..=i32::min(abs[0], input.range))
+				.map(|dx|
+				{
+					let dy = input.range - dx;
+					([action.pos[0] - sign[0] * dx, action.pos[1] - sign[1] * dy], dist - input.range)
+				})
+				.collect()
+		},
+		ActionKind::Plant =>
+		{
+			// Like `metric::resolve` the robot stays in range or moves to the edge of the range, around obstacles
+			// the nearest free cells may be anywhere in the range
+			let cells: Vec<[i32; 2]> = if input.obstacles.is_empty()
+			{
+				input.metric.edge(action.pos, input.range).collect()
+			}
+			else
+			{
+				input.metric.ball(action.pos, input.range).filter(|&cell| !distances.is_blocked(cell)).collect()
+			};
+
+			let stay = (input.metric.distance(pos, action.pos) <= input.range).then_some((pos, 0));
+
+			stay.into_iter()
+				.chain(cells.into_iter().filter(|&cell| cell != pos).map(|cell| (cell, distances.distance(pos, cell))))
+				.filter(|&(_, dist)| dist < UNREACHABLE)
+				.collect()
+		},
+		ActionKind::Collect => vec![(action.pos, distances.distance(pos, action.pos))],
+	}
+}
+
+
+fn find_position(cells: &[([i32; 2], i32)], pos: [i32; 2]) -> Option<i32>
+{
+	cells.binary_search_by_key(&pos, |(cell, _)| *cell).ok().map(|index| cells[index].1)
+}
+
+
+// Resolve the actions like `resolve` while keeping every equally optimal position instead of a single one
+pub fn realize(input: &Input, actions: &[Action]) -> Realization
+{
+	let actions = compatible_actions(input, actions);
+	let oracle = Distances::new(input);
+
+	let mut layers: Vec<Layer> = vec![[(input.start, 0)].into()];
+
+	for action in actions
+	{
+		let mut layer = Layer::new();
+
+		for (&pos, &distance_traveled) in layers.last().unwrap()
+		{
+			for (new_pos, dist) in successors(input, &oracle, pos, action)
+			{
+				let new_distance_traveled = distance_traveled + dist;
+
+				if new_distance_traveled + oracle.return_distance(new_pos) > input.max_distance as i32
+				{
+					continue;
+				}
+
+				let best = layer.entry(new_pos).or_insert(new_distance_traveled);
+				*best = i32::min(*best, new_distance_traveled);
+			}
+		}
+
+		if layer.is_empty()
+		{
+			break;
+		}
+
+		layers.push(layer);
+	}
+
+	// Like `resolve`, the plan ends with the plant giving the highest value
+	let distances: Vec<i32> = layers.iter().map(|layer| layer.iter().map(|(&pos, &dist)| dist + oracle.return_distance(pos)).min().unwrap()).collect();
+	let end = best_end(input, actions, &distances);
+
+	let actions = actions[0..end].to_vec();
+	let plant_count = actions.iter().filter(|action| action.kind == ActionKind::Plant).count();
+	let value = planted_value(input, &actions, plant_count);
+	let distance_traveled = distances[end];
+
+	// Walk back from the optimal end positions, a position is kept if it leads to a kept position of the next layer
+	// with the minimum distance of that position, every optimal plan only goes through such positions
+	let mut positions: Vec<Vec<([i32; 2], i32)>> = vec![Vec::new(); end + 1];
+
+	positions[end] = layers[end].iter()
+		.filter(|(&pos, &dist)| dist + oracle.return_distance(pos) == distance_traveled)
+		.map(|(&pos, &dist)| (pos, dist))
+		.collect();
+	positions[end].sort_unstable();
+
+	for index in (0..end).rev()
+	{
+		let next_positions = &positions[index + 1];
+
+		let mut cells: Vec<([i32; 2], i32)> = layers[index].iter()
+			.filter(|(&pos, &dist)|
+			{
+				successors(input, &oracle, pos, &actions[index]).into_iter()
+					.any(|(new_pos, new_dist)| find_position(next_positions, new_pos) == Some(dist + new_dist))
+			})
+			.map(|(&pos, &dist)| (pos, dist))
+			.collect();
+
+		cells.sort_unstable();
+		positions[index] = cells;
+	}
+
+	// The first layer is the start position, only the positions of the actions are returned
+	positions.remove(0);
+
+	Realization
+	{
+		plant_count,
+		value,
+		distance_traveled,
+		actions,
+		positions,
+	}
+}
+
+
+impl Realization
+{
+	// Number of optimal positions the robot can choose from for each action
+	pub fn slack(&self) -> Vec<usize>
+	{
+		self.positions.iter().map(|cells| cells.len()).collect()
+	}
+
+	// Choose a concrete position for each action among the optimal ones
+	pub fn pick(&self, input: &Input, tie_break: TieBreak) -> Vec<OutAction>
+	{
+		let oracle = Distances::new(input);
+		let mut moves = Vec::new();
+
+		let mut pos = input.start;
+		let mut distance_traveled = 0;
+
+		for (index, action) in self.actions.iter().enumerate()
+		{
+			let next_seed = self.actions[index+1..].iter().find(|action| action.kind == ActionKind::Collect).map(|action| action.pos);
+
+			let key = |new_pos: [i32; 2]| match (tie_break, next_seed)
+			{
+				(TieBreak::NextSeed, Some(seed)) => (oracle.distance(new_pos, seed), new_pos),
+				_ => (0, new_pos),
+			};
+
+			// Stay on an optimal path: the successor must be optimal and reached with its minimum distance
+			let (new_pos, dist) = successors(input, &oracle, pos, action).into_iter()
+				.filter(|&(new_pos, dist)| find_position(&self.positions[index], new_pos) == Some(distance_traveled + dist))
+				.min_by_key(|&(new_pos, _)| key(new_pos))
+				.unwrap();
+
+			if new_pos != pos
+			{
+				moves.extend(oracle.waypoints(pos, new_pos).into_iter().map(OutAction::Move));
+			}
+
+			moves.push(action.as_output());
+
+			pos = new_pos;
+			distance_traveled += dist;
+		}
+
+		if input.return_to_base && pos != input.start
+		{
+			moves.extend(oracle.waypoints(pos, input.start).into_iter().map(OutAction::Move));
+		}
+
+		debug_assert_eq!(distance_traveled + oracle.return_distance(pos), self.distance_traveled);
+
+		moves
+	}
+}
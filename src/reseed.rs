@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+
+use crate::io::Input;
+use crate::metric::PositionSet;
+use crate::quantum::QRect;
+use crate::{resolve_fast, Action, ActionKind};
+
+
+// Seeds carried, the last pile collected, `None` before the first collect, and the piles collected on the way that
+// are still tried before a later plant, sorted. With the plant index, the first two fix the robot position: the pile
+// and the plants planted since.
+type Key = (u32, Option<usize>, Vec<usize>);
+
+#[derive(Debug, Clone)]
+struct Step
+{
+	dist: i32,
+	pos: QRect,
+	prev: Key,
+	// Pile collected right before the plant
+	collected: Option<usize>,
+}
+
+fn push(layer: &mut BTreeMap<Key, Step>, key: Key, step: Step)
+{
+	if layer.get(&key).is_none_or(|old| step.dist < old.dist)
+	{
+		layer.insert(key, step);
+	}
+}
+
+// The `count` piles nearest to each plant
+fn candidate_piles(input: &Input, plants: &[[i32; 2]], count: usize) -> Vec<Vec<usize>>
+{
+	plants.iter()
+		.map(|&plant|
+		{
+			let mut piles: Vec<usize> = (0..input.seeds.len()).collect();
+			let count = count.min(piles.len());
+
+			if count > 0 && count < piles.len()
+			{
+				piles.select_nth_unstable_by_key(count - 1, |&pile| (input.metric.distance(input.seeds[pile], plant), pile));
+			}
+
+			piles.truncate(count);
+			piles
+		})
+		.collect()
+}
+
+// Shortest interleaving of collects with the plants in this order, with the distance including the return leg.
+// The robot collects before a plant when it has room for seeds, at any pile not used on the way to the state.
+// The states are the plant index, the seeds carried and the piles used, so the count of states grows with the
+// subsets of piles: `insert_seeds_near` is the bounded version for the large inputs.
+// `None` when the seeds cannot cover the plants, and for the inputs it does not model: other metrics, obstacles,
+// species and pile sizes.
+pub fn insert_seeds(input: &Input, plants: &[[i32; 2]]) -> Option<(Vec<Action>, i32)>
+{
+	insert_seeds_near(input, plants, input.seeds.len())
+}
+
+// `insert_seeds` where the robot only collects at the `candidates` piles nearest to the next plant. A pile is
+// forgotten after the last plant it is tried before, which keeps the sets of piles used small.
+pub fn insert_seeds_near(input: &Input, plants: &[[i32; 2]], candidates: usize) -> Option<(Vec<Action>, i32)>
+{
+	if !input.exact_qpos() || input.has_species() || input.has_piles()
+	{
+		return None;
+	}
+
+	let candidates = candidate_piles(input, plants, candidates);
+
+	// Last plant each pile is tried before
+	let mut last_tried = vec![0; input.seeds.len()];
+	for (index, piles) in candidates.iter().enumerate()
+	{
+		for &pile in piles
+		{
+			last_tried[pile] = index;
+		}
+	}
+
+	let capacity = input.seed_capacity;
+	let start_key = (capacity, None, Vec::new());
+	let start = Step { dist: 0, pos: QRect::from_cell(input.start), prev: start_key.clone(), collected: None };
+
+	let mut layers = vec![BTreeMap::from([(start_key, start)])];
+
+	for (index, &plant) in plants.iter().enumerate()
+	{
+		let mut layer = BTreeMap::new();
+
+		for (key, step) in layers.last().unwrap()
+		{
+			let (storage, last, used) = key;
+			let kept: Vec<usize> = used.iter().copied().filter(|&pile| last_tried[pile] > index).collect();
+
+			if *storage > 0
+			{
+				let (pos, dist) = step.pos.apply_plant(input, plant);
+				push(&mut layer, (storage - 1, *last, kept.clone()), Step { dist: step.dist + dist, pos, prev: key.clone(), collected: None });
+			}
+
+			if *storage < capacity
+			{
+				for &pile in &candidates[index]
+				{
+					if used.contains(&pile)
+					{
+						continue;
+					}
+
+					let (seed_pos, seed_dist) = step.pos.apply_seed(input.seeds[pile]);
+					let (pos, dist) = seed_pos.apply_plant(input, plant);
+
+					let mut kept = kept.clone();
+					if last_tried[pile] > index
+					{
+						let position = kept.partition_point(|&other| other < pile);
+						kept.insert(position, pile);
+					}
+
+					push(&mut layer, (capacity - 1, Some(pile), kept), Step { dist: step.dist + seed_dist + dist, pos, prev: key.clone(), collected: Some(pile) });
+				}
+			}
+		}
+
+		if layer.is_empty()
+		{
+			return None;
+		}
+
+		layers.push(layer);
+	}
+
+	let (mut key, distance_traveled) = layers.last().unwrap().iter()
+		.map(|(key, step)| (key.clone(), step.dist + step.pos.return_distance(input)))
+		.min_by_key(|(_, dist)| *dist)?;
+
+	let mut actions = Vec::new();
+
+	for (index, layer) in layers.iter().enumerate().skip(1).rev()
+	{
+		let step = &layer[&key];
+
+		actions.push(Action { pos: plants[index - 1], kind: ActionKind::Plant });
+
+		if let Some(pile) = step.collected
+		{
+			actions.push(Action { pos: input.seeds[pile], kind: ActionKind::Collect });
+		}
+
+		key = step.prev.clone();
+	}
+
+	actions.reverse();
+
+	Some((actions, distance_traveled))
+}
+
+pub fn plant_order(actions: &[Action]) -> Vec<[i32; 2]>
+{
+	actions.iter()
+		.filter(|action| action.kind == ActionKind::Plant)
+		.map(|action| action.pos)
+		.collect()
+}
+
+// Keep the plant order of the actions and place the collects again, when it plants as much for less distance
+pub fn reseed(input: &Input, actions: &[Action]) -> Vec<Action>
+{
+	let Some((new_actions, _)) = insert_seeds(input, &plant_order(actions))
+	else
+	{
+		return actions.to_vec();
+	};
+
+	let (plant_count, distance_traveled) = resolve_fast(input, actions, true);
+	let (new_plant_count, new_distance_traveled) = resolve_fast(input, &new_actions, true);
+
+	if (new_plant_count, -new_distance_traveled) > (plant_count, -distance_traveled)
+	{
+		new_actions
+	}
+	else
+	{
+		actions.to_vec()
+	}
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use crate::io::Input;
+use crate::{planted_value, resolve_fast, Action, ActionKind};
+
+
+// A plan with the pile to go to when a seed pile of the plan turns out to be empty, the piles are indices in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RobustPlan
+{
+	pub actions: Vec<Action>,
+	pub fallbacks: BTreeMap<usize, usize>,
+}
+
+// Averages over the sampled failures
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evaluation
+{
+	pub expected_plants: f64,
+	pub expected_value: f64,
+	// Share of the samples where the plan goes over the distance budget
+	pub overrun_rate: f64,
+}
+
+
+fn failure_probability(input: &Input, pile: usize) -> f64
+{
+	input.failure_probabilities.get(pile).copied().unwrap_or(0.0)
+}
+
+fn species(input: &Input, pile: usize) -> Option<u32>
+{
+	input.species.get(pile).copied()
+}
+
+// Pile of each collect of the actions: piles sharing a position are taken one after the other, `None` for a
+// position with no pile
+fn collected_piles(input: &Input, actions: &[Action]) -> Vec<Option<usize>>
+{
+	let mut taken = vec![false; input.seeds.len()];
+
+	actions.iter()
+		.filter(|action| action.kind == ActionKind::Collect)
+		.map(|action|
+		{
+			let mut piles = (0..input.seeds.len()).filter(|&pile| input.seeds[pile] == action.pos);
+			let pile = piles.clone().find(|&pile| !taken[pile]).or_else(|| piles.next())?;
+
+			taken[pile] = true;
+			Some(pile)
+		})
+		.collect()
+}
+
+// Each pile of the plan falls back to the closest pile of the same species the plan does not use,
+// the spare piles fall back to each other so that several failures can be chained
+pub fn robust_plan(input: &Input, actions: &[Action]) -> RobustPlan
+{
+	let used: Vec<usize> = collected_piles(input, actions).into_iter().flatten().collect();
+
+	let spares: Vec<usize> = (0..input.seeds.len())
+		.filter(|pile| !used.contains(pile))
+		.collect();
+
+	let fallbacks = used.iter()
+		.chain(&spares)
+		.filter_map(|&pile|
+		{
+			let seed = input.seeds[pile];
+
+			// The less likely to fail first, then the closest
+			let fallback = spares.iter()
+				.copied()
+				.filter(|&spare| spare != pile && species(input, spare) == species(input, pile))
+				.min_by(|&a, &b| failure_probability(input, a).total_cmp(&failure_probability(input, b))
+					.then(input.travel_distance(seed, input.seeds[a]).cmp(&input.travel_distance(seed, input.seeds[b]))))?;
+
+			Some((pile, fallback))
+		})
+		.collect();
+
+	RobustPlan { actions: actions.to_vec(), fallbacks }
+}
+
+// Actions done when the piles in `failed` are empty. An empty pile is replaced by its fallbacks until one has seeds,
+// the robot goes on with its seeds left when there is none and stops before a plant it has no seed for.
+pub fn replay(input: &Input, plan: &RobustPlan, failed: &[usize]) -> Vec<Action>
+{
+	let mut piles = collected_piles(input, &plan.actions).into_iter();
+
+	let mut seed_storage = input.seed_capacity;
+	let mut visited = vec![false; input.seeds.len()];
+
+	let mut actions = Vec::new();
+
+	for action in &plan.actions
+	{
+		match action.kind
+		{
+			ActionKind::Plant =>
+			{
+				if seed_storage == 0
+				{
+					break;
+				}
+
+				seed_storage -= 1;
+				actions.push(*action);
+			},
+			ActionKind::Collect =>
+			{
+				let Some(mut pile) = piles.next().unwrap()
+				else
+				{
+					actions.push(*action);
+					seed_storage = input.seed_capacity;
+					continue;
+				};
+
+				// A pile already visited is skipped for its own fallback, the hops are bounded in case they loop
+				for _ in 0..=input.seeds.len()
+				{
+					if !visited[pile]
+					{
+						visited[pile] = true;
+						actions.push(Action { pos: input.seeds[pile], kind: ActionKind::Collect });
+
+						if !failed.contains(&pile)
+						{
+							seed_storage = input.seed_capacity;
+							break;
+						}
+					}
+
+					let Some(&fallback) = plan.fallbacks.get(&pile)
+					else
+					{
+						break;
+					};
+
+					pile = fallback;
+				}
+			},
+		}
+	}
+
+	actions
+}
+
+// Monte-Carlo estimate of the plants planted and of the budget overruns, each pile fails with its probability.
+// `None` with species or pile sizes, the failures are only replayed with a single seed stock.
+pub fn evaluate<R: Rng>(input: &Input, plan: &RobustPlan, samples: usize, rng: &mut R) -> Option<Evaluation>
+{
+	if input.has_species() || input.has_piles()
+	{
+		return None;
+	}
+
+	let mut plants = 0;
+	let mut value = 0;
+	let mut overruns = 0;
+
+	for _ in 0..samples
+	{
+		let failed: Vec<usize> = (0..input.seeds.len())
+			.filter(|&pile| rng.random_bool(failure_probability(input, pile)))
+			.collect();
+
+		let actions = replay(input, plan, &failed);
+
+		let (plant_count, _) = resolve_fast(input, &actions, true);
+		plants += plant_count;
+		value += planted_value(input, &actions, plant_count);
+
+		let (_, distance_traveled) = resolve_fast(input, &actions, false);
+		if distance_traveled > input.max_distance as i32
+		{
+			overruns += 1;
+		}
+	}
+
+	let samples = samples.max(1) as f64;
+
+	Some(Evaluation
+	{
+		expected_plants: plants as f64 / samples,
+		expected_value: value as f64 / samples,
+		overrun_rate: overruns as f64 / samples,
+	})
+}
@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+
+use crate::io::Input;
+use crate::{Action, ActionKind};
+
+
+// Clarke-Wright savings: every plant starts as its own trip from its nearest seed pile, then the trips are joined
+// end to end by decreasing savings while they have at most `seed_capacity` plants. The trips are then chained
+// into a single tour, collecting at a seed pile between two trips.
+pub fn savings(input: &Input) -> Vec<Action>
+{
+	let capacity = input.seed_capacity as usize;
+
+	if capacity == 0
+	{
+		return Vec::new();
+	}
+
+	let plants = &input.plants;
+
+	// Distance from each plant to its nearest seed pile, the start stands for the piles when there is none
+	let depot: Vec<i32> = plants.iter()
+		.map(|&plant| input.seeds.iter()
+			.map(|&seed| input.travel_distance(seed, plant))
+			.min()
+			.unwrap_or_else(|| input.travel_distance(input.start, plant)))
+		.collect();
+
+	let mut pairs: Vec<(i32, usize, usize)> = (0..plants.len())
+		.flat_map(|i| (i+1..plants.len()).map(move |j| (i, j)))
+		.map(|(i, j)| (depot[i] + depot[j] - input.travel_distance(plants[i], plants[j]), i, j))
+		.filter(|&(saving, _, _)| saving > 0)
+		.collect();
+
+	pairs.sort_unstable_by_key(|&(saving, i, j)| (-saving, i, j));
+
+	let mut trips: Vec<VecDeque<usize>> = (0..plants.len()).map(|index| VecDeque::from([index])).collect();
+	let mut trip_of: Vec<usize> = (0..plants.len()).collect();
+
+	for (_saving, i, j) in pairs
+	{
+		let (a, b) = (trip_of[i], trip_of[j]);
+
+		if a == b || trips[a].len() + trips[b].len() > capacity
+		{
+			continue;
+		}
+
+		// The trips are joined through i and j, which must be at one of their ends
+		if trips[a].back() != Some(&i)
+		{
+			if trips[a].front() != Some(&i)
+			{
+				continue;
+			}
+			trips[a].make_contiguous().reverse();
+		}
+
+		if trips[b].front() != Some(&j)
+		{
+			if trips[b].back() != Some(&j)
+			{
+				continue;
+			}
+			trips[b].make_contiguous().reverse();
+		}
+
+		let trip = std::mem::take(&mut trips[b]);
+
+		for &index in &trip
+		{
+			trip_of[index] = a;
+		}
+
+		trips[a].extend(trip);
+	}
+
+	let trips: Vec<Vec<[i32; 2]>> = trips.into_iter()
+		.filter(|trip| !trip.is_empty())
+		.map(|trip| trip.into_iter().map(|index| plants[index]).collect())
+		.collect();
+
+	chain(input, trips)
+}
+
+// Nearest trip next, either way round. The first trip uses the seeds the robot starts with, the next ones
+// collect at the pile closest to the way from the end of the previous trip.
+fn chain(input: &Input, mut trips: Vec<Vec<[i32; 2]>>) -> Vec<Action>
+{
+	let mut seeds = input.seeds.clone();
+	let mut pos = input.start;
+
+	let mut actions = Vec::new();
+
+	while !trips.is_empty()
+	{
+		let first = actions.is_empty();
+
+		let Some((_, trip_index, reversed, seed_index)) = trips.iter()
+			.enumerate()
+			.flat_map(|(index, trip)| [(index, trip[0], false), (index, trip[trip.len()-1], true)])
+			.filter_map(|(index, trip_start, reversed)|
+			{
+				if first
+				{
+					return Some((input.travel_distance(pos, trip_start), index, reversed, None));
+				}
+
+				seeds.iter()
+					.enumerate()
+					.map(|(seed_index, &seed)| (input.travel_distance(pos, seed) + input.travel_distance(seed, trip_start), index, reversed, Some(seed_index)))
+					.min()
+			})
+			.min()
+		else
+		{
+			break;
+		};
+
+		if let Some(seed_index) = seed_index
+		{
+			actions.push(Action { pos: seeds.remove(seed_index), kind: ActionKind::Collect });
+		}
+
+		let mut trip = trips.remove(trip_index);
+
+		if reversed
+		{
+			trip.reverse();
+		}
+
+		pos = trip[trip.len()-1];
+		actions.extend(trip.into_iter().map(|plant| Action { pos: plant, kind: ActionKind::Plant }));
+	}
+
+	actions
+}
@@ -0,0 +1,259 @@
+use crate::cell_set::CellSet;
+use crate::io::Input;
+use crate::metric::PositionSet;
+use crate::obstacles::Distances;
+use crate::piles::{pickup, pile_sizes};
+use crate::quantum::QRect;
+use crate::species::Inventory;
+use crate::{resolve_fast, Action, ActionKind};
+
+
+// Insertions checked with a full resolution for each plant, among the cheapest ones that keep the windows
+const VERIFIED_INSERTIONS: usize = 3;
+
+
+// Time at which each action starts, the robot waits when it reaches a plant before its window opens.
+// The robot follows the shortest route, the one the resolvers choose.
+pub fn start_times(input: &Input, actions: &[Action]) -> Vec<u32>
+{
+	timings(input, actions).into_iter().map(|(_, start)| start).collect()
+}
+
+// Time at which the robot reaches each action and the time the action starts
+fn timings(input: &Input, actions: &[Action]) -> Vec<(u32, u32)>
+{
+	if input.exact_qpos()
+	{
+		timings_sets::<QRect>(input, actions)
+	}
+	else
+	{
+		timings_sets::<CellSet>(input, actions)
+	}
+}
+
+fn timings_sets<P: PositionSet>(input: &Input, actions: &[Action]) -> Vec<(u32, u32)>
+{
+	let mut pos = P::from_cell(input.start);
+	let mut time = 0;
+
+	actions.iter()
+		.map(|action|
+		{
+			let (new_pos, dist) = pos.apply_action(input, action);
+			pos = new_pos;
+			time += dist as u32;
+
+			let arrival = time;
+
+			let (start, duration) = match action.kind
+			{
+				ActionKind::Plant => (time.max(input.window(action.pos)[0]), input.plant_duration),
+				ActionKind::Collect => (time, input.collect_duration),
+			};
+
+			time = start + duration;
+			(arrival, start)
+		})
+		.collect()
+}
+
+// Number of actions before the first plant the robot reaches after its window closed
+pub fn timed_prefix(input: &Input, actions: &[Action]) -> usize
+{
+	start_times(input, actions).iter()
+		.zip(actions)
+		.position(|(&start, action)| action.kind == ActionKind::Plant && start > input.window(action.pos)[1])
+		.unwrap_or(actions.len())
+}
+
+
+// Collects added to a plant order, the robot goes to the pile closest to its way to the next plant when it
+// has no seed for it. `None` when some plant cannot get a seed.
+fn with_seeds(input: &Input, route: &[[i32; 2]]) -> Option<Vec<Action>>
+{
+	let mut inventory = Inventory::new(input);
+	let mut piles = pile_sizes(input);
+	let mut pos = input.start;
+
+	let mut actions = Vec::new();
+
+	for (index, &plant) in route.iter().enumerate()
+	{
+		if !inventory.can_plant(input, plant)
+		{
+			if inventory.is_full(input)
+			{
+				return None;
+			}
+
+			let pile = (0..input.seeds.len())
+				.filter(|&pile| piles[pile] > 0 && input.accepts(plant, input.seed_species(input.seeds[pile])))
+				.min_by_key(|&pile| input.travel_distance(pos, input.seeds[pile]) + input.travel_distance(input.seeds[pile], plant))?;
+
+			let seed_pos = input.seeds[pile];
+			let species = input.seed_species(seed_pos);
+			let needed = route[index..].iter().filter(|&&plant| input.accepts(plant, species)).count() as u32;
+
+			let (count, left) = pickup(input, inventory.total(), piles[pile], needed);
+
+			// Without pile sizes, a pile is only visited once
+			piles[pile] = if input.has_piles() { left } else { 0 };
+			inventory.add(input, seed_pos, count);
+
+			actions.push(Action { pos: seed_pos, kind: ActionKind::Collect });
+		}
+
+		inventory.plant(input, plant);
+		actions.push(Action { pos: plant, kind: ActionKind::Plant });
+		pos = plant;
+	}
+
+	Some(actions)
+}
+
+// A plant route with its plan and the timing of the plan
+struct Schedule
+{
+	route: Vec<[i32; 2]>,
+	actions: Vec<Action>,
+	// Index in `actions` of each plant of the route
+	plant_actions: Vec<usize>,
+	arrivals: Vec<i64>,
+	starts: Vec<i64>,
+	ends: Vec<i64>,
+	// Delay of the start of each action that every plant after it can take without missing its window,
+	// the robot waiting before a window opens absorbs part of the delay
+	max_shifts: Vec<i64>,
+	distance_traveled: i32,
+}
+
+impl Schedule
+{
+	fn new(input: &Input, route: Vec<[i32; 2]>, actions: Vec<Action>, distance_traveled: i32) -> Self
+	{
+		let timings = timings(input, &actions);
+
+		let duration = |action: &Action| match action.kind
+		{
+			ActionKind::Plant => input.plant_duration,
+			ActionKind::Collect => input.collect_duration,
+		};
+
+		let arrivals: Vec<i64> = timings.iter().map(|&(arrival, _)| arrival as i64).collect();
+		let starts: Vec<i64> = timings.iter().map(|&(_, start)| start as i64).collect();
+		let ends: Vec<i64> = starts.iter().zip(&actions).map(|(&start, action)| start + duration(action) as i64).collect();
+
+		let mut max_shifts = vec![i64::MAX; actions.len()];
+		for index in (0..actions.len()).rev()
+		{
+			let close = match actions[index].kind
+			{
+				ActionKind::Plant => input.window(actions[index].pos)[1] as i64,
+				ActionKind::Collect => i64::MAX,
+			};
+
+			// A delay of the next action first uses up the time the robot waits there
+			let next = (index + 1 < actions.len()).then(|| max_shifts[index + 1].saturating_add(starts[index + 1] - arrivals[index + 1]));
+
+			max_shifts[index] = next.map_or(close - starts[index], |next| next.min(close - starts[index]));
+		}
+
+		let plant_actions = (0..actions.len()).filter(|&index| actions[index].kind == ActionKind::Plant).collect();
+
+		Schedule { route, actions, plant_actions, arrivals, starts, ends, max_shifts, distance_traveled }
+	}
+
+	// Distance added by inserting the plant before the plant `index` of the route, `None` when a plant would miss
+	// its window. The plan keeps its collects, the robot goes from the action before to the plant and to the action
+	// after, so that each insertion is checked in constant time.
+	fn insertion(&self, input: &Input, distances: &Distances, index: usize, plant: [i32; 2]) -> Option<i32>
+	{
+		let (prev_pos, prev_end, next) = match index
+		{
+			0 => (input.start, 0, 0),
+			_ =>
+			{
+				let prev = self.plant_actions[index - 1];
+				(self.actions[prev].pos, self.ends[prev], prev + 1)
+			},
+		};
+
+		let [open, close] = input.window(plant);
+
+		let start = (prev_end + distances.distance(prev_pos, plant) as i64).max(open as i64);
+		if start > close as i64
+		{
+			return None;
+		}
+
+		let Some(next_action) = self.actions.get(next)
+		else
+		{
+			return Some(distances.distance(prev_pos, plant) + distances.return_distance(plant) - distances.return_distance(prev_pos));
+		};
+
+		// The delay at the next action, with the distances between the positions of the actions on both sides
+		let delay = start + input.plant_duration as i64 + distances.distance(plant, next_action.pos) as i64 - prev_end - distances.distance(prev_pos, next_action.pos) as i64;
+		let wait = self.starts[next] - self.arrivals[next];
+
+		if (delay - wait).max(0) > self.max_shifts[next]
+		{
+			return None;
+		}
+
+		Some(distances.distance(prev_pos, plant) + distances.distance(plant, next_action.pos) - distances.distance(prev_pos, next_action.pos))
+	}
+}
+
+// Plans the route with its collects, `None` when a plant is not planted on time within the distance budget
+fn schedule(input: &Input, route: Vec<[i32; 2]>) -> Option<Schedule>
+{
+	let actions = with_seeds(input, &route)?;
+	let (plant_count, distance_traveled) = resolve_fast(input, &actions, true);
+
+	(plant_count == route.len()).then(|| Schedule::new(input, route, actions, distance_traveled))
+}
+
+// Plants by earliest deadline, each one is inserted where it lengthens the route the least while every plant
+// of the route is still planted on time and within the distance budget. The insertions are ranked with the
+// timing of the current plan, only the best ones are checked with a full resolution. The plants that fit
+// nowhere are skipped.
+pub fn earliest_deadline(input: &Input) -> Vec<Action>
+{
+	let distances = Distances::new(input);
+
+	let mut order = input.plants.clone();
+	order.sort_by_key(|&plant|
+	{
+		let [open, close] = input.window(plant);
+		(close, open)
+	});
+
+	let mut current = Schedule::new(input, Vec::new(), Vec::new(), 0);
+
+	for plant in order
+	{
+		let mut insertions: Vec<(i32, usize)> = (0..=current.route.len())
+			.filter_map(|index| Some((current.insertion(input, &distances, index, plant)?, index)))
+			.filter(|&(added, _)| current.distance_traveled + added <= input.max_distance as i32)
+			.collect();
+		insertions.sort_unstable();
+
+		let inserted = insertions.into_iter()
+			.take(VERIFIED_INSERTIONS)
+			.find_map(|(_, index)|
+			{
+				let mut route = current.route.clone();
+				route.insert(index, plant);
+				schedule(input, route)
+			});
+
+		if let Some(inserted) = inserted
+		{
+			current = inserted;
+		}
+	}
+
+	current.actions
+}
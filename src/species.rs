@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use crate::io::Input;
+use crate::piles::{pickup, pile_sizes};
+use crate::{Action, ActionKind};
+
+
+// Seeds carried by the robot by species. The seeds the robot starts with have no species and fit every plant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Inventory
+{
+	counts: BTreeMap<Option<u32>, u32>,
+}
+
+impl Inventory
+{
+	pub fn new(input: &Input) -> Self
+	{
+		let mut counts = BTreeMap::new();
+
+		if input.seed_capacity > 0
+		{
+			counts.insert(None, input.seed_capacity);
+		}
+
+		Inventory { counts }
+	}
+
+	pub fn total(&self) -> u32
+	{
+		self.counts.values().sum()
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.counts.is_empty()
+	}
+
+	pub fn is_full(&self, input: &Input) -> bool
+	{
+		self.total() >= input.seed_capacity
+	}
+
+	pub fn can_plant(&self, input: &Input, plant_pos: [i32; 2]) -> bool
+	{
+		self.counts.keys().any(|&species| input.accepts(plant_pos, species))
+	}
+
+	// Fill the inventory up to the capacity with the species of the seed pile
+	pub fn collect(&mut self, input: &Input, seed_pos: [i32; 2])
+	{
+		self.add(input, seed_pos, input.seed_capacity.saturating_sub(self.total()));
+	}
+
+	pub fn add(&mut self, input: &Input, seed_pos: [i32; 2], count: u32)
+	{
+		if count > 0
+		{
+			*self.counts.entry(input.seed_species(seed_pos)).or_insert(0) += count;
+		}
+	}
+
+	// Use the compatible species accepted by the fewest plants of the input, the seeds without species last.
+	// The choice only depends on the input so that every solver and resolver agree on the inventory.
+	pub fn plant(&mut self, input: &Input, plant_pos: [i32; 2]) -> bool
+	{
+		let demand = |species: Option<u32>| species.map_or(usize::MAX, |species| input.demand(species));
+
+		let Some(species) = self.counts.keys()
+			.copied()
+			.filter(|&species| input.accepts(plant_pos, species))
+			.min_by_key(|&species| (demand(species), species))
+		else
+		{
+			return false;
+		};
+
+		let count = self.counts.get_mut(&species).unwrap();
+		*count -= 1;
+
+		if *count == 0
+		{
+			self.counts.remove(&species);
+		}
+
+		true
+	}
+}
+
+
+// Number of plants after the action `index` accepting the species of the seed pile
+fn seeds_needed(input: &Input, actions: &[Action], index: usize) -> u32
+{
+	let species = input.seed_species(actions[index].pos);
+
+	actions[index+1..].iter()
+		.filter(|action| action.kind == ActionKind::Plant && input.accepts(action.pos, species))
+		.count() as u32
+}
+
+// Number of actions the robot can do before planting a plant none of its seeds fit, or running out of seeds
+pub fn feasible_prefix(input: &Input, actions: &[Action]) -> usize
+{
+	let mut inventory = Inventory::new(input);
+	let mut piles = pile_sizes(input);
+
+	for (index, action) in actions.iter().enumerate()
+	{
+		match action.kind
+		{
+			ActionKind::Plant =>
+			{
+				if !inventory.plant(input, action.pos)
+				{
+					return index;
+				}
+			},
+			ActionKind::Collect =>
+			{
+				if !input.seeds.contains(&action.pos)
+				{
+					inventory.collect(input, action.pos);
+					continue;
+				}
+
+				// Piles sharing a position are taken from one after the other
+				let mut needed = seeds_needed(input, actions, index);
+
+				for pile in (0..input.seeds.len()).filter(|&pile| input.seeds[pile] == action.pos)
+				{
+					let (count, left) = pickup(input, inventory.total(), piles[pile], needed);
+					piles[pile] = left;
+					needed = needed.saturating_sub(count);
+					inventory.add(input, action.pos, count);
+				}
+			},
+		}
+	}
+
+	actions.len()
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use crate::io::Input;
+use crate::lin_kernighan::{candidate_lists, cost, reach};
+use crate::{score, Action, ActionKind};
+
+
+const CANDIDATES: usize = 5;
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Move
+{
+	Swap(usize, usize),
+	// The action at the first index is removed and inserted at the second one
+	Relocate(usize, usize),
+}
+
+impl Move
+{
+	fn indices(self) -> (usize, usize)
+	{
+		match self
+		{
+			Move::Swap(i, j) | Move::Relocate(i, j) => (i, j),
+		}
+	}
+
+	fn apply(self, order: &mut [usize])
+	{
+		match self
+		{
+			Move::Swap(i, j) => order.swap(i, j),
+			Move::Relocate(i, j) if i < j => order[i..=j].rotate_left(1),
+			Move::Relocate(i, j) => order[j..=i].rotate_right(1),
+		}
+	}
+
+	// Index before the move of the action at `index` after it
+	fn source(self, index: usize) -> usize
+	{
+		match self
+		{
+			Move::Swap(i, j) if index == i => j,
+			Move::Swap(i, j) if index == j => i,
+			Move::Relocate(i, j) if i < j && (i..j).contains(&index) => index + 1,
+			Move::Relocate(i, j) if index == j => i,
+			Move::Relocate(i, j) if j < i && (j + 1..=i).contains(&index) => index - 1,
+			_ => index,
+		}
+	}
+
+	// Actions moved by the move and the indices they end at, the actions shifted by a relocate do not count
+	fn placements(self, order: &[usize]) -> Vec<(usize, usize)>
+	{
+		match self
+		{
+			Move::Swap(i, j) => vec![(order[i], j), (order[j], i)],
+			Move::Relocate(i, j) => vec![(order[i], j)],
+		}
+	}
+
+	// The run of plants between two collects around `index` fits in the seed storage once the move is done
+	fn fits_at(self, input: &Input, actions: &[Action], order: &[usize], index: usize) -> bool
+	{
+		let is_collect = |moved: usize| actions[order[self.source(moved)]].kind == ActionKind::Collect;
+
+		let after = (index..order.len()).take_while(|&moved| !is_collect(moved)).take(input.seed_capacity as usize + 1).count();
+		let before = (0..index).rev().take_while(|&moved| !is_collect(moved)).take(input.seed_capacity as usize + 1).count();
+
+		after + before <= input.seed_capacity as usize
+	}
+
+	fn feasible(self, input: &Input, actions: &[Action], order: &[usize]) -> bool
+	{
+		match self
+		{
+			Move::Swap(i, j) => self.fits_at(input, actions, order, i) && self.fits_at(input, actions, order, j),
+			Move::Relocate(i, j) => self.fits_at(input, actions, order, i.min(order.len() - 1)) && self.fits_at(input, actions, order, j),
+		}
+	}
+
+	// Edges removed and edges added by the move, the edges between the actions shifted by a relocate stay
+	fn edges(self) -> (Vec<usize>, Vec<usize>)
+	{
+		match self
+		{
+			Move::Swap(i, j) =>
+			{
+				let mut edges = vec![i, i + 1, j, j + 1];
+				edges.dedup();
+				(edges.clone(), edges)
+			},
+			Move::Relocate(i, j) if i < j => (vec![i, i + 1, j + 1], vec![i, j, j + 1]),
+			Move::Relocate(i, j) => (vec![j, i, i + 1], vec![j, j + 1, i + 1]),
+		}
+	}
+
+	// Change of the edge costs, from the few edges the move changes
+	fn delta(self, input: &Input, actions: &[Action], order: &[usize]) -> i32
+	{
+		let (removed, added) = self.edges();
+
+		let before = removed.iter().map(|&edge| edge_cost(input, actions, order, edge, |index| index)).sum::<i32>();
+		let after = added.iter().map(|&edge| edge_cost(input, actions, order, edge, |index| self.source(index))).sum::<i32>();
+
+		after - before
+	}
+}
+
+
+// Cost of the edge from the action before `edge` to the one at `edge`, the start is before the first action.
+// `source` gives the index in `order` of each index of the plan.
+fn edge_cost(input: &Input, actions: &[Action], order: &[usize], edge: usize, source: impl Fn(usize) -> usize) -> i32
+{
+	let from = match edge
+	{
+		0 => (input.start, 0),
+		_ => reach(input, &actions[order[source(edge - 1)]]),
+	};
+	let to = (edge < order.len()).then(|| reach(input, &actions[order[source(edge)]]));
+
+	cost(input, from, to)
+}
+
+// Swaps with the nearest actions, and relocates next to them on either side
+fn moves(order: &[usize], positions: &[usize], candidates: &[Vec<usize>]) -> Vec<Move>
+{
+	let mut moves: Vec<Move> = order.iter()
+		.enumerate()
+		.flat_map(|(i, &action)| candidates[action].iter().map(move |&candidate| (i, positions[candidate])))
+		.flat_map(|(i, j)|
+		{
+			let beside = if i < j { j - 1 } else { j + 1 };
+			[Move::Swap(i.min(j), i.max(j)), Move::Relocate(i, j), Move::Relocate(i, beside)]
+		})
+		.filter(|&mv| mv.indices().0 != mv.indices().1)
+		.collect();
+
+	moves.sort_unstable();
+	moves.dedup();
+	moves
+}
+
+
+// Tabu search: at each iteration the best move among swaps and relocates near the nearest actions is done,
+// even when it makes the plan worse. The moves are ranked by the change of the edge costs, a plant being done from
+// anywhere within the range. Putting an action back at an index it left less than `tenure` iterations ago is tabu,
+// unless the resolved plan after the move beats the best plan found: only the tabu moves ranked before the best
+// allowed one and the plan after the move done are resolved.
+// Returns the best plan found.
+pub fn tabu_search(input: &Input, actions: &[Action], iterations: usize, tenure: usize) -> Vec<Action>
+{
+	let candidates = candidate_lists(input, actions, CANDIDATES);
+
+	let plan = |order: &[usize]| -> Vec<Action> { order.iter().map(|&index| actions[index]).collect() };
+
+	// Actions by index in `actions`, and the index of each one in the plan
+	let mut order: Vec<usize> = (0..actions.len()).collect();
+	let mut positions = order.clone();
+
+	let mut best = order.clone();
+	let mut best_score = score(input, actions);
+
+	// Iteration until which an action may not go back to an index
+	let mut tabu: HashMap<(usize, usize), usize> = HashMap::new();
+
+	for iteration in 0..iterations
+	{
+		let mut ranked: Vec<(i32, Move)> = moves(&order, &positions, &candidates)
+			.into_iter()
+			.filter(|mv| mv.feasible(input, actions, &order))
+			.map(|mv| (mv.delta(input, actions, &order), mv))
+			.collect();
+
+		ranked.sort_unstable();
+
+		// The score of the plan after a tabu move is known once it was checked for the aspiration
+		let chosen = ranked.into_iter().find_map(|(_, mv)|
+		{
+			let is_tabu = mv.placements(&order).iter().any(|placement| tabu.get(placement).is_some_and(|&until| iteration < until));
+
+			if !is_tabu
+			{
+				return Some((mv, None));
+			}
+
+			let mut moved = order.clone();
+			mv.apply(&mut moved);
+			let moved_score = score(input, &plan(&moved));
+
+			(moved_score > best_score).then_some((mv, Some(moved_score)))
+		});
+
+		let Some((mv, moved_score)) = chosen
+		else
+		{
+			break;
+		};
+
+		for (action, _) in mv.placements(&order)
+		{
+			tabu.insert((action, positions[action]), iteration + 1 + tenure);
+		}
+
+		mv.apply(&mut order);
+
+		let (i, j) = mv.indices();
+		for index in i.min(j)..=i.max(j)
+		{
+			positions[order[index]] = index;
+		}
+
+		let moved_score = moved_score.unwrap_or_else(|| score(input, &plan(&order)));
+
+		if moved_score > best_score
+		{
+			best = order.clone();
+			best_score = moved_score;
+		}
+	}
+
+	plan(&best)
+}
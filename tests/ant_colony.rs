@@ -0,0 +1,85 @@
+mod common;
+
+use codingup_qualifs::ant_colony::ant_colony;
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::metric::Metric;
+use codingup_qualifs::{resolve_q_fast, ActionKind};
+use common::{check_cases, collect, is_valid_plan, plant, rng, random_input};
+
+
+#[test]
+fn ants_build_valid_plans_within_budget()
+{
+	check_cases("ants_build_valid_plans_within_budget", 100, |rng| random_input(rng, 10, 5), |input|
+	{
+		let actions = ant_colony(input, 5, 5, &mut rng(0));
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("ant_colony gave {actions:?}"));
+		}
+
+		// The ants never go over the budget, every plant they choose is planted
+		let plants = actions.iter().filter(|action| action.kind == ActionKind::Plant).count();
+		let (plant_count, _) = resolve_q_fast(input, &actions, true);
+		if plant_count != plants
+		{
+			return Err(format!("resolve_q_fast plants {plant_count} of {actions:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn ants_learn_the_greedy_tour_on_a_line()
+{
+	// Plants on a line with a pile in the middle, the nearest neighbour tour is optimal
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 3,
+		range: 0,
+		seeds: vec![[6, 1]],
+		plants: (1..=6).map(|x| [2 * x, 0]).collect(),
+		..Default::default()
+	};
+
+	let actions = ant_colony(&input, 10, 20, &mut rng(0));
+
+	assert_eq!(actions, vec![plant([2, 0]), plant([4, 0]), plant([6, 0]), collect([6, 1]), plant([8, 0]), plant([10, 0]), plant([12, 0])]);
+	assert_eq!(resolve_q_fast(&input, &actions, true), resolve_q_fast(&input, &nearest_neighbour(&input), true));
+}
+
+#[test]
+fn ants_are_reproducible()
+{
+	let input = random_input(&mut rng(3), 12, 5);
+
+	assert_eq!(ant_colony(&input, 4, 6, &mut rng(1)), ant_colony(&input, 4, 6, &mut rng(1)));
+}
+
+#[test]
+fn ants_go_around_obstacles()
+{
+	let mut input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 15,
+		"seedCapacity": 2,
+		"range": 1,
+		"seeds": [],
+		"plants": [[6, 0], [0, 2]],
+		"obstacles": [{ "min": [3, -4], "max": [3, 4] }, [4, 0]]
+	}"#).unwrap();
+
+	// [0, 2] is on the way up to pass the wall
+	let actions = ant_colony(&input, 5, 5, &mut rng(0));
+	assert_eq!(resolve_q_fast(&input, &actions, true), (2, 15));
+
+	input.obstacles.clear();
+	input.metric = Metric::Chebyshev;
+
+	let actions = ant_colony(&input, 5, 5, &mut rng(0));
+	assert_eq!(resolve_q_fast(&input, &actions, true), (2, 1 + 4));
+}
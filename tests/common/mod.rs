@@ -0,0 +1,331 @@
+#![allow(dead_code)]
+
+use codingup_qualifs::io::Input;
+use codingup_qualifs::{Action, ActionKind};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+
+pub fn rng(seed: u64) -> StdRng
+{
+	StdRng::seed_from_u64(seed)
+}
+
+pub fn plant(pos: [i32; 2]) -> Action
+{
+	Action { pos, kind: ActionKind::Plant }
+}
+
+pub fn collect(pos: [i32; 2]) -> Action
+{
+	Action { pos, kind: ActionKind::Collect }
+}
+
+pub fn random_input(rng: &mut StdRng, max_plants: usize, max_seeds: usize) -> Input
+{
+	let size = rng.random_range(4..=16);
+	let random_pos = |rng: &mut StdRng| [rng.random_range(0..=size), rng.random_range(0..=size)];
+
+	let plant_count = rng.random_range(1..=max_plants);
+	let seed_count = rng.random_range(0..=max_seeds);
+
+	Input
+	{
+		max_distance: rng.random_range(0..=4 * size as u32),
+		seed_capacity: rng.random_range(1..=3),
+		range: rng.random_range(0..=3),
+		seeds: (0..seed_count).map(|_| random_pos(rng)).collect(),
+		plants: (0..plant_count).map(|_| random_pos(rng)).collect(),
+		..Default::default()
+	}
+}
+
+// Each plant and seed is used at most once and the robot never plants without a seed
+pub fn is_valid_plan(input: &Input, actions: &[Action]) -> bool
+{
+	let mut seed_storage = input.seed_capacity;
+	let mut plants = input.plants.clone();
+	let mut seeds = input.seeds.clone();
+
+	for action in actions
+	{
+		let pool = match action.kind
+		{
+			ActionKind::Plant => &mut plants,
+			ActionKind::Collect => &mut seeds,
+		};
+
+		let Some(index) = pool.iter().position(|pos| *pos == action.pos)
+		else
+		{
+			return false;
+		};
+
+		pool.swap_remove(index);
+
+		match action.kind
+		{
+			ActionKind::Plant =>
+			{
+				if seed_storage == 0
+				{
+					return false;
+				}
+				seed_storage -= 1;
+			},
+			ActionKind::Collect => seed_storage = input.seed_capacity,
+		}
+	}
+
+	true
+}
+
+
+pub trait Shrink: Sized
+{
+	// Smaller variations of the value, the first failing one is kept
+	fn shrink(&self) -> Vec<Self>;
+}
+
+fn run_property<T>(property: &impl Fn(&T) -> Result<(), String>, case: &T) -> Result<(), String>
+{
+	std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| property(case)))
+		.unwrap_or_else(|err|
+		{
+			let message = err.downcast_ref::<String>().cloned()
+				.or_else(|| err.downcast_ref::<&str>().map(|message| message.to_string()))
+				.unwrap_or_default();
+			Err(format!("panic: {message}"))
+		})
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T
+{
+	std::env::var(name).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+}
+
+// Run `property` on `TEST_CASES` cases generated from the seeds starting at `TEST_SEED`.
+// A failing case is shrunk before being reported.
+pub fn check<T: Shrink + std::fmt::Debug>(name: &str, generate: impl Fn(&mut StdRng) -> T, property: impl Fn(&T) -> Result<(), String>)
+{
+	check_cases(name, 1000, generate, property);
+}
+
+// `check` with fewer cases by default, for the slow solvers
+pub fn check_cases<T: Shrink + std::fmt::Debug>(name: &str, default_count: u64, generate: impl Fn(&mut StdRng) -> T, property: impl Fn(&T) -> Result<(), String>)
+{
+	let base_seed: u64 = env_or("TEST_SEED", 0);
+	let case_count: u64 = env_or("TEST_CASES", default_count);
+
+	for seed in base_seed..base_seed + case_count
+	{
+		let mut case = generate(&mut rng(seed));
+
+		let Err(mut error) = run_property(&property, &case)
+		else
+		{
+			continue;
+		};
+
+		'shrink: loop
+		{
+			for candidate in case.shrink()
+			{
+				if let Err(candidate_error) = run_property(&property, &candidate)
+				{
+					case = candidate;
+					error = candidate_error;
+					continue 'shrink;
+				}
+			}
+
+			break;
+		}
+
+		panic!("{name} failed with TEST_SEED={seed}: {error}\nMinimal case: {case:?}");
+	}
+}
+
+
+fn shrink_pos(pos: [i32; 2]) -> Vec<[i32; 2]>
+{
+	let mut res = Vec::new();
+	for axis in 0..2
+	{
+		if pos[axis] != 0
+		{
+			let mut new_pos = pos;
+			new_pos[axis] /= 2;
+			res.push(new_pos);
+			new_pos[axis] = pos[axis] - pos[axis].signum();
+			res.push(new_pos);
+		}
+	}
+	res
+}
+
+pub fn shrink_value(value: i32) -> Vec<i32>
+{
+	match value
+	{
+		0 => vec![],
+		_ => vec![0, value / 2, value - value.signum()],
+	}
+}
+
+
+// An action order to resolve, the input lists the positions used by the actions
+#[derive(Debug, Clone)]
+pub struct PlanCase
+{
+	pub input: Input,
+	pub actions: Vec<Action>,
+}
+
+impl PlanCase
+{
+	pub fn new(input: &Input, actions: Vec<Action>) -> Self
+	{
+		let mut input = input.clone();
+		input.plants = actions.iter().filter(|action| action.kind == ActionKind::Plant).map(|action| action.pos).collect();
+		input.seeds = actions.iter().filter(|action| action.kind == ActionKind::Collect).map(|action| action.pos).collect();
+
+		PlanCase { input, actions }
+	}
+
+	pub fn random(rng: &mut StdRng) -> Self
+	{
+		let input = random_input(rng, 8, 3);
+
+		let mut actions: Vec<Action> = input.plants.iter().map(|&pos| Action { pos, kind: ActionKind::Plant })
+			.chain(input.seeds.iter().map(|&pos| Action { pos, kind: ActionKind::Collect }))
+			.collect();
+
+		actions.shuffle(rng);
+
+		PlanCase::new(&input, actions)
+	}
+}
+
+impl Shrink for PlanCase
+{
+	fn shrink(&self) -> Vec<Self>
+	{
+		let mut res = Vec::new();
+
+		for index in 0..self.actions.len()
+		{
+			let mut actions = self.actions.clone();
+			actions.remove(index);
+			res.push(PlanCase::new(&self.input, actions));
+		}
+
+		for index in 0..self.actions.len()
+		{
+			for pos in shrink_pos(self.actions[index].pos)
+			{
+				let mut actions = self.actions.clone();
+				actions[index].pos = pos;
+				res.push(PlanCase::new(&self.input, actions));
+			}
+		}
+
+		for range in shrink_value(self.input.range)
+		{
+			let mut case = self.clone();
+			case.input.range = range;
+			res.push(case);
+		}
+
+		for max_distance in shrink_value(self.input.max_distance as i32)
+		{
+			let mut case = self.clone();
+			case.input.max_distance = max_distance as u32;
+			res.push(case);
+		}
+
+		res
+	}
+}
+
+
+// Inputs shrink by dropping plants and seed piles with their per-plant and per-pile data, then by smaller values
+impl Shrink for Input
+{
+	fn shrink(&self) -> Vec<Self>
+	{
+		let mut res = Vec::new();
+
+		for index in 0..self.plants.len()
+		{
+			let mut input = self.clone();
+			input.plants.remove(index);
+			remove_at(&mut input.values, index);
+			remove_at(&mut input.requirements, index);
+			remove_at(&mut input.windows, index);
+			res.push(input);
+		}
+
+		for index in 0..self.seeds.len()
+		{
+			let mut input = self.clone();
+			input.seeds.remove(index);
+			remove_at(&mut input.species, index);
+			remove_at(&mut input.pile_sizes, index);
+			remove_at(&mut input.failure_probabilities, index);
+			res.push(input);
+		}
+
+		for (index, &pos) in self.plants.iter().enumerate()
+		{
+			for pos in shrink_pos(pos)
+			{
+				let mut input = self.clone();
+				input.plants[index] = pos;
+				res.push(input);
+			}
+		}
+
+		for (index, &pos) in self.seeds.iter().enumerate()
+		{
+			for pos in shrink_pos(pos)
+			{
+				let mut input = self.clone();
+				input.seeds[index] = pos;
+				res.push(input);
+			}
+		}
+
+		for range in shrink_value(self.range)
+		{
+			res.push(Input { range, ..self.clone() });
+		}
+
+		for max_distance in shrink_value(self.max_distance as i32)
+		{
+			res.push(Input { max_distance: max_distance as u32, ..self.clone() });
+		}
+
+		for seed_capacity in shrink_value(self.seed_capacity as i32).into_iter().filter(|&capacity| capacity > 0)
+		{
+			res.push(Input { seed_capacity: seed_capacity as u32, ..self.clone() });
+		}
+
+		for input in &mut res
+		{
+			input.index();
+		}
+
+		res
+	}
+}
+
+// The per-plant and per-pile lists are empty when the feature is not used
+fn remove_at<T>(list: &mut Vec<T>, index: usize)
+{
+	if index < list.len()
+	{
+		list.remove(index);
+	}
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use codingup_qualifs::coverage::{expand, route_stops, stops, Stop};
+use codingup_qualifs::io::Input;
+use codingup_qualifs::metric::Metric;
+use codingup_qualifs::{distance, resolve_q_fast, Action, ActionKind};
+use common::{check_cases, is_valid_plan, plant, random_input};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+
+#[test]
+fn stops_cover_every_plant_once()
+{
+	check_cases("stops_cover_every_plant_once", 200, |rng| random_input(rng, 30, 5), |input|
+	{
+		let stops = stops(input);
+
+		for stop in &stops
+		{
+			if stop.plants.is_empty() || stop.plants.len() > input.seed_capacity as usize
+			{
+				return Err(format!("{stop:?} holds {} plants", stop.plants.len()));
+			}
+			if stop.plants.iter().any(|&plant| distance(plant, stop.pos) > input.range)
+			{
+				return Err(format!("{stop:?} is out of range"));
+			}
+		}
+
+		let mut plants: Vec<[i32; 2]> = stops.iter().flat_map(|stop| stop.plants.clone()).collect();
+		let mut expected = input.plants.clone();
+		plants.sort_unstable();
+		expected.sort_unstable();
+
+		if plants != expected
+		{
+			return Err(format!("the stops hold the plants {plants:?}"));
+		}
+		if expand(&stops).len() != input.plants.len()
+		{
+			return Err(format!("{stops:?} expands to {} plants", expand(&stops).len()));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn one_stop_reaches_a_diamond()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 4,
+		range: 1,
+		plants: vec![[4, 1], [6, 1], [5, 0], [5, 2]],
+		..Default::default()
+	};
+
+	let stops = stops(&input);
+
+	assert_eq!(stops.len(), 1);
+	assert_eq!(stops[0].pos, [5, 1]);
+
+	let actions = route_stops(&input, &stops);
+	assert_eq!(actions, vec![plant([4, 1]), plant([6, 1]), plant([5, 0]), plant([5, 2])]);
+	assert_eq!(resolve_q_fast(&input, &actions, true), (4, 6));
+}
+
+#[test]
+fn stops_reach_plants_with_odd_rotated_corners()
+{
+	// The lowest rotated coordinates of the two plants give a cell of the wrong parity
+	let input = Input
+	{
+		seed_capacity: 2,
+		range: 1,
+		plants: vec![[0, 0], [0, 1]],
+		..Default::default()
+	};
+
+	assert_eq!(stops(&input).len(), 1);
+}
+
+#[test]
+fn groups_are_capped_by_the_seed_capacity()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 2,
+		range: 3,
+		seeds: vec![[0, 1]],
+		plants: vec![[5, 0], [5, 1], [6, 0], [6, 1], [5, 2]],
+		..Default::default()
+	};
+
+	let stops = stops(&input);
+	assert_eq!(stops.iter().map(|stop| stop.plants.len()).collect::<Vec<_>>(), [2, 2, 1]);
+
+	let actions = route_stops(&input, &stops);
+	assert!(is_valid_plan(&input, &actions), "{actions:?}");
+	assert_eq!(actions.iter().filter(|action| action.kind == ActionKind::Collect).collect::<Vec<_>>(), [&Action { pos: [0, 1], kind: ActionKind::Collect }]);
+}
+
+#[test]
+fn stop_routes_are_valid()
+{
+	let generate = |rng: &mut StdRng|
+	{
+		let mut input = random_input(rng, 30, 5);
+		input.return_to_base = rng.random_bool(0.5);
+		input
+	};
+
+	check_cases("stop_routes_are_valid", 200, generate, |input|
+	{
+		let actions = route_stops(input, &stops(input));
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("route_stops gave {actions:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn one_stop_reaches_a_square_with_chebyshev()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 4,
+		range: 1,
+		plants: vec![[4, 0], [6, 0], [4, 2], [6, 2]],
+		metric: Metric::Chebyshev,
+		..Default::default()
+	};
+
+	let stops = stops(&input);
+
+	assert_eq!(stops, vec![Stop { pos: [5, 1], plants: input.plants.clone() }]);
+
+	let actions = route_stops(&input, &stops);
+	assert_eq!(resolve_q_fast(&input, &actions, true), (4, 5));
+}
+
+#[test]
+fn stops_avoid_obstacles_and_follow_the_species()
+{
+	let input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 100,
+		"seedCapacity": 1,
+		"range": 1,
+		"seeds": [[0, 1], [0, -1]],
+		"species": [0, 1],
+		"plants": [[4, 1], [6, 1]],
+		"requirements": [[], [1]],
+		"obstacles": [[5, 1]]
+	}"#).unwrap();
+
+	let stops = stops(&input);
+
+	// No stop on the blocked cell between the plants, each one is planted from its own stop
+	assert_eq!(stops.len(), 2);
+	assert!(stops.iter().all(|stop| stop.pos != [5, 1]));
+
+	// The pile of species 0 fits no plant left after the first one
+	let actions = route_stops(&input, &stops);
+	assert_eq!(actions[1], Action { pos: [0, -1], kind: ActionKind::Collect });
+	assert_eq!(resolve_q_fast(&input, &actions, true).0, 2);
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use codingup_qualifs::decompose::{cluster_order, clusters, decompose};
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::metric::Metric;
+use codingup_qualifs::resolve_q_fast;
+use common::{check_cases, collect, is_valid_plan, plant, random_input};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+
+#[test]
+fn single_cluster_matches_inner_solver()
+{
+	check_cases("single_cluster_matches_inner_solver", 100, |rng| random_input(rng, 10, 5), |input|
+	{
+		let actions = decompose(input, 1, nearest_neighbour);
+		let expected = nearest_neighbour(input);
+
+		if actions.as_ref() != Some(&expected)
+		{
+			return Err(format!("decompose gave {actions:?} for {expected:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn clusters_split_the_plants()
+{
+	check_cases("clusters_split_the_plants", 100, |rng| random_input(rng, 20, 8), |input|
+	{
+		let clusters = clusters(input, 4);
+
+		let mut plants: Vec<[i32; 2]> = clusters.iter().flat_map(|cluster| cluster.plants.clone()).collect();
+		let mut expected = input.plants.clone();
+		plants.sort_unstable();
+		expected.sort_unstable();
+
+		if plants != expected
+		{
+			return Err(format!("the clusters hold the plants {plants:?}"));
+		}
+		if clusters.iter().map(|cluster| cluster.seeds.len()).sum::<usize>() != input.seeds.len()
+		{
+			return Err(format!("the clusters hold {:?} seeds", clusters.iter().map(|cluster| cluster.seeds.len()).collect::<Vec<_>>()));
+		}
+		if clusters.len() > 4 || clusters.iter().any(|cluster| cluster.plants.is_empty())
+		{
+			return Err(format!("{} clusters, some of them empty", clusters.len()));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn clusters_are_visited_along_a_line()
+{
+	let input = Input
+	{
+		max_distance: 1000,
+		seed_capacity: 2,
+		range: 0,
+		seeds: vec![[30, 0], [10, 0], [20, 0]],
+		plants: vec![[31, 0], [29, 0], [11, 0], [9, 0], [21, 0], [19, 0]],
+		..Default::default()
+	};
+
+	let clusters = clusters(&input, 3);
+	let centers: Vec<[i32; 2]> = cluster_order(&input, &clusters).into_iter().map(|index| clusters[index].center).collect();
+
+	assert_eq!(centers, [[10, 0], [20, 0], [30, 0]]);
+}
+
+#[test]
+fn joins_refill_the_seed_storage()
+{
+	// The robot leaves the first cluster with one seed left, the second cluster needs two
+	let input = Input
+	{
+		max_distance: 1000,
+		seed_capacity: 2,
+		range: 0,
+		seeds: vec![[0, 1], [20, 0]],
+		plants: vec![[1, 0], [20, 1], [21, 1]],
+		..Default::default()
+	};
+
+	let actions = decompose(&input, 2, nearest_neighbour).unwrap();
+
+	assert!(is_valid_plan(&input, &actions), "{actions:?}");
+	assert_eq!(actions, vec![plant([1, 0]), collect([20, 0]), plant([20, 1]), plant([21, 1])]);
+	assert_eq!(resolve_q_fast(&input, &actions, true), (3, 22));
+}
+
+#[test]
+fn decomposed_plans_are_valid()
+{
+	let generate = |rng: &mut StdRng|
+	{
+		let mut input = random_input(rng, 20, 8);
+		input.return_to_base = rng.random_bool(0.5);
+		input
+	};
+
+	check_cases("decomposed_plans_are_valid", 100, generate, |input|
+	{
+		for cluster_count in 1..=5
+		{
+			let Some(actions) = decompose(input, cluster_count, nearest_neighbour)
+			else
+			{
+				return Err(format!("no plan with {cluster_count} clusters"));
+			};
+
+			if !is_valid_plan(input, &actions)
+			{
+				return Err(format!("{cluster_count} clusters gave {actions:?}"));
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn decomposition_follows_the_metric_and_the_obstacles()
+{
+	let mut input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 1000,
+		"seedCapacity": 2,
+		"range": 0,
+		"seeds": [],
+		"plants": [[2, 0], [6, 0]],
+		"obstacles": [{ "min": [4, -4], "max": [4, 4] }]
+	}"#).unwrap();
+
+	// One cluster per plant, the second one is reached around the wall
+	let actions = decompose(&input, 2, nearest_neighbour).unwrap();
+	assert_eq!(resolve_q_fast(&input, &actions, true), (2, 2 + 5 + 4 + 5));
+
+	input.obstacles.clear();
+	input.metric = Metric::Chebyshev;
+	input.plants = vec![[2, 2], [6, 2]];
+
+	let actions = decompose(&input, 2, nearest_neighbour).unwrap();
+	assert_eq!(resolve_q_fast(&input, &actions, true), (2, 2 + 4));
+
+	input.species = vec![0];
+	input.seeds = vec![[1, 1]];
+	assert_eq!(decompose(&input, 2, nearest_neighbour), None);
+}
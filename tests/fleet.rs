@@ -0,0 +1,99 @@
+mod common;
+
+use codingup_qualifs::fleet::{evaluate, solve};
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::{Input, Robot};
+use codingup_qualifs::{resolve, ActionKind};
+use common::{check, collect, plant, random_input};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+
+fn random_fleet(rng: &mut StdRng) -> Input
+{
+	let mut input = random_input(rng, 8, 3);
+
+	input.robots = (0..rng.random_range(1..=3))
+		.map(|_| Robot
+		{
+			start: [rng.random_range(0..=16), rng.random_range(0..=16)],
+			max_distance: rng.random_range(0..=40),
+			seed_capacity: rng.random_range(1..=3),
+		})
+		.collect();
+
+	input
+}
+
+
+#[test]
+fn robots_take_the_plants_next_to_them()
+{
+	let input = Input
+	{
+		range: 0,
+		seeds: vec![[0, 1], [10, 1]],
+		plants: vec![[1, 0], [2, 0], [9, 0], [8, 0]],
+		robots: vec![Robot { start: [0, 0], max_distance: 10, seed_capacity: 1 }, Robot { start: [10, 0], max_distance: 10, seed_capacity: 1 }],
+		..Default::default()
+	};
+
+	let plans = solve(&input, nearest_neighbour);
+
+	assert_eq!(plans, vec![vec![plant([1, 0]), collect([0, 1]), plant([2, 0])], vec![plant([9, 0]), collect([10, 1]), plant([8, 0])]]);
+	assert_eq!(evaluate(&input, &plans), (4, 4, 12));
+}
+
+#[test]
+fn single_robot_matches_constructor()
+{
+	check("single_robot_matches_constructor", |rng| random_input(rng, 8, 3), |input|
+	{
+		let plans = solve(input, nearest_neighbour);
+		let (_, plant_count, distance_traveled) = resolve(input, &nearest_neighbour(input));
+
+		if plans != vec![nearest_neighbour(input)]
+		{
+			return Err(format!("fleet {plans:?}"));
+		}
+
+		let evaluation = evaluate(input, &plans);
+		if evaluation != (plant_count, plant_count as u32, distance_traveled)
+		{
+			return Err(format!("evaluated {evaluation:?} for {:?}", (plant_count, distance_traveled)));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn robots_share_the_pool()
+{
+	check("robots_share_the_pool", random_fleet, |input|
+	{
+		let plans = solve(input, nearest_neighbour);
+
+		if plans.len() != input.robots.len()
+		{
+			return Err(format!("{} plans for {} robots", plans.len(), input.robots.len()));
+		}
+
+		// Every plant and seed is used by at most one robot
+		for (kind, pool) in [(ActionKind::Plant, &input.plants), (ActionKind::Collect, &input.seeds)]
+		{
+			let mut pool = pool.clone();
+			for action in plans.iter().flatten().filter(|action| action.kind == kind)
+			{
+				let Some(index) = pool.iter().position(|pos| *pos == action.pos)
+				else
+				{
+					return Err(format!("{:?} used twice in {plans:?}", action.pos));
+				};
+				pool.swap_remove(index);
+			}
+		}
+
+		Ok(())
+	});
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use codingup_qualifs::genetic::genetic;
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::score;
+use common::{check_cases, collect, is_valid_plan, plant, rng, random_input};
+
+
+#[test]
+fn the_pile_is_pulled_between_the_plants()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[2, 0]],
+		plants: vec![[3, 0], [1, 0]],
+		..Default::default()
+	};
+
+	let actions = genetic(&input, &[], 6, 10, &mut rng(0));
+
+	assert_eq!(actions, vec![plant([1, 0]), collect([2, 0]), plant([3, 0])]);
+	assert_eq!(score(&input, &actions), (2, -3));
+}
+
+#[test]
+fn genetic_keeps_the_best_initial_plan()
+{
+	check_cases("genetic_keeps_the_best_initial_plan", 50, |rng| random_input(rng, 8, 4), |input|
+	{
+		let initial = nearest_neighbour(input);
+
+		let actions = genetic(input, std::slice::from_ref(&initial), 10, 5, &mut rng(0));
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("genetic gave {actions:?}"));
+		}
+		if score(input, &actions) < score(input, &initial)
+		{
+			return Err(format!("{actions:?} is worse than {initial:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn genetic_builds_valid_plans_from_random_orders()
+{
+	check_cases("genetic_builds_valid_plans_from_random_orders", 50, |rng| random_input(rng, 8, 4), |input|
+	{
+		let actions = genetic(input, &[], 8, 10, &mut rng(0));
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("genetic gave {actions:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn genetic_is_reproducible()
+{
+	let input = random_input(&mut rng(7), 10, 5);
+
+	assert_eq!(genetic(&input, &[], 12, 8, &mut rng(1)), genetic(&input, &[], 12, 8, &mut rng(1)));
+}
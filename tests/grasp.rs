@@ -0,0 +1,152 @@
+mod common;
+
+use std::time::Duration;
+
+use codingup_qualifs::grasp::{construct, grasp, Config};
+use codingup_qualifs::io::Input;
+use codingup_qualifs::lin_kernighan::lin_kernighan;
+use codingup_qualifs::metric::Metric;
+use codingup_qualifs::{resolve_q_fast, score, Action, ActionKind};
+use common::{check_cases, is_valid_plan, rng, random_input};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+
+fn lin_kernighan_improver(input: &Input, actions: &mut [Action])
+{
+	let improved = lin_kernighan(input, actions, 3);
+	actions.copy_from_slice(&improved);
+}
+
+fn config(alpha: f64, max_iterations: usize) -> Config
+{
+	Config { alpha, time_limit: Duration::from_secs(60), max_iterations }
+}
+
+
+#[test]
+fn zero_alpha_is_deterministic()
+{
+	check_cases("zero_alpha_is_deterministic", 50, |rng| random_input(rng, 12, 5), |input|
+	{
+		let first = construct(input, 0.0, &mut rng(1));
+		let second = construct(input, 0.0, &mut rng(2));
+
+		if first != second
+		{
+			return Err(format!("{first:?} then {second:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn constructions_are_valid_within_the_distance()
+{
+	let generate = |rng: &mut StdRng|
+	{
+		let mut input = random_input(rng, 12, 5);
+		input.return_to_base = rng.random_bool(0.5);
+		input
+	};
+
+	check_cases("constructions_are_valid_within_the_distance", 200, generate, |input|
+	{
+		let actions = construct(input, 0.5, &mut rng(0));
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("construct gave {actions:?}"));
+		}
+
+		let plants = actions.iter().filter(|action| action.kind == ActionKind::Plant).count();
+		let (plant_count, _) = resolve_q_fast(input, &actions, true);
+		if plant_count != plants
+		{
+			return Err(format!("resolve_q_fast plants {plant_count} of {actions:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn grasp_returns_its_best_plan()
+{
+	check_cases("grasp_returns_its_best_plan", 30, |rng| random_input(rng, 12, 5), |input|
+	{
+		let (actions, statistics) = grasp(input, &mut lin_kernighan_improver, config(0.3, 8), &mut rng(0));
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("grasp gave {actions:?}"));
+		}
+		if statistics.iterations != 8
+		{
+			return Err(format!("{} iterations", statistics.iterations));
+		}
+		if statistics.best != score(input, &actions)
+		{
+			return Err(format!("{statistics:?} for {actions:?}"));
+		}
+		if statistics.worst > statistics.best || statistics.mean_value < statistics.worst.0 as f64 || statistics.mean_value > statistics.best.0 as f64
+		{
+			return Err(format!("{statistics:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn grasp_is_reproducible()
+{
+	let input = random_input(&mut rng(9), 15, 6);
+	let run = || grasp(&input, &mut |_: &Input, _: &mut [Action]| (), config(0.5, 20), &mut rng(4));
+
+	assert_eq!(run(), run());
+}
+
+#[test]
+fn grasp_stops_at_the_time_limit()
+{
+	let input = random_input(&mut rng(3), 15, 6);
+	let config = Config { alpha: 0.5, time_limit: Duration::ZERO, max_iterations: usize::MAX };
+
+	let (actions, statistics) = grasp(&input, &mut lin_kernighan_improver, config, &mut rng(0));
+
+	assert!(actions.is_empty());
+	assert_eq!(statistics.iterations, 0);
+}
+
+#[test]
+fn constructions_follow_the_species_and_the_metric()
+{
+	// The pile of species 0 fits no plant, the robot goes to the one of species 1
+	let mut input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[1, 0], [0, -1]],
+		species: vec![0, 1],
+		plants: vec![[2, 0], [0, 2]],
+		requirements: vec![vec![1], vec![1]],
+		..Default::default()
+	};
+
+	let expected = vec![
+		Action { pos: [2, 0], kind: ActionKind::Plant },
+		Action { pos: [0, -1], kind: ActionKind::Collect },
+		Action { pos: [0, 2], kind: ActionKind::Plant },
+	];
+
+	assert_eq!(construct(&input, 0.0, &mut rng(0)), expected);
+	assert_eq!(resolve_q_fast(&input, &expected, true), (2, 2 + 3 + 3));
+
+	input.metric = Metric::Chebyshev;
+
+	assert_eq!(construct(&input, 0.0, &mut rng(0)), expected);
+	assert_eq!(resolve_q_fast(&input, &expected, true), (2, 2 + 2 + 3));
+}
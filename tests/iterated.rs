@@ -0,0 +1,94 @@
+mod common;
+
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::iterated::{iterated_local_search, Acceptance, Config, Kick};
+use codingup_qualifs::lin_kernighan::lin_kernighan;
+use codingup_qualifs::{score, Action};
+use common::{check_cases, is_valid_plan, plant, rng, random_input};
+
+
+fn lin_kernighan_improver(input: &Input, actions: &mut [Action])
+{
+	let improved = lin_kernighan(input, actions, 3);
+	actions.copy_from_slice(&improved);
+}
+
+
+#[test]
+fn kicks_alone_sort_the_line()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 4,
+		range: 0,
+		plants: vec![[1, 0], [2, 0], [3, 0], [4, 0]],
+		..Default::default()
+	};
+
+	let initial = vec![plant([3, 0]), plant([1, 0]), plant([4, 0]), plant([2, 0])];
+	let config = Config { iterations: 50, kick: Kick::DoubleBridge, acceptance: Acceptance::Better, checkpoint_every: 0 };
+
+	let actions = iterated_local_search(&input, &initial, &mut |_: &Input, _: &mut [Action]| (), config, &mut rng(0), |_| ());
+
+	assert_eq!(actions, vec![plant([1, 0]), plant([2, 0]), plant([3, 0]), plant([4, 0])]);
+	assert_eq!(score(&input, &actions), (4, -4));
+}
+
+#[test]
+fn search_never_loses_the_best_plan()
+{
+	check_cases("search_never_loses_the_best_plan", 20, |rng| random_input(rng, 10, 5), |input|
+	{
+		let initial = nearest_neighbour(input);
+
+		for kick in [Kick::DoubleBridge, Kick::SegmentShuffle]
+		{
+			for acceptance in [Acceptance::Better, Acceptance::Equal, Acceptance::Worse(5)]
+			{
+				let config = Config { iterations: 10, kick, acceptance, checkpoint_every: 0 };
+				let actions = iterated_local_search(input, &initial, &mut lin_kernighan_improver, config, &mut rng(0), |_| ());
+
+				if !is_valid_plan(input, &actions)
+				{
+					return Err(format!("{kick:?} and {acceptance:?} gave {actions:?}"));
+				}
+				if score(input, &actions) < score(input, &initial)
+				{
+					return Err(format!("{kick:?} and {acceptance:?} gave {actions:?}, worse than {initial:?}"));
+				}
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn checkpoints_only_save_new_best_plans()
+{
+	let input = random_input(&mut rng(11), 12, 6);
+	let initial = nearest_neighbour(&input);
+
+	let mut checkpoints = Vec::new();
+	let config = Config { iterations: 50, kick: Kick::SegmentShuffle, acceptance: Acceptance::Worse(1000), checkpoint_every: 5 };
+
+	let actions = iterated_local_search(&input, &initial, &mut |_: &Input, _: &mut [Action]| (), config, &mut rng(0), |best| checkpoints.push(score(&input, best)));
+
+	assert!(!checkpoints.is_empty() && checkpoints.len() <= 10, "{checkpoints:?}");
+	assert!(checkpoints.windows(2).all(|pair| pair[0] < pair[1]), "{checkpoints:?}");
+	assert_eq!(checkpoints.last(), Some(&score(&input, &actions)));
+}
+
+#[test]
+fn search_is_reproducible()
+{
+	let input = random_input(&mut rng(5), 12, 6);
+	let initial = nearest_neighbour(&input);
+	let config = Config { iterations: 20, kick: Kick::DoubleBridge, acceptance: Acceptance::Equal, checkpoint_every: 0 };
+
+	let run = || iterated_local_search(&input, &initial, &mut lin_kernighan_improver, config, &mut rng(2), |_| ());
+
+	assert_eq!(run(), run());
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::lin_kernighan::lin_kernighan;
+use codingup_qualifs::score;
+use common::{check_cases, is_valid_plan, plant, random_input};
+
+
+#[test]
+fn crossing_routes_are_untangled()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 10,
+		range: 0,
+		plants: vec![[1, 0], [2, 0], [3, 0], [4, 0], [5, 0]],
+		..Default::default()
+	};
+
+	let actions = lin_kernighan(&input, &[plant([1, 0]), plant([4, 0]), plant([3, 0]), plant([2, 0]), plant([5, 0])], 3);
+
+	assert_eq!(score(&input, &actions), (5, -5));
+}
+
+#[test]
+fn the_actions_after_the_start_are_reversed()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 10,
+		range: 0,
+		plants: vec![[1, 0], [2, 0], [3, 0], [4, 0]],
+		..Default::default()
+	};
+
+	let actions = lin_kernighan(&input, &[plant([3, 0]), plant([2, 0]), plant([1, 0]), plant([4, 0])], 3);
+
+	assert_eq!(actions, vec![plant([1, 0]), plant([2, 0]), plant([3, 0]), plant([4, 0])]);
+}
+
+#[test]
+fn the_gain_follows_the_range()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 10,
+		range: 1,
+		plants: vec![[0, 1], [4, 4], [2, 0], [1, 5]],
+		..Default::default()
+	};
+
+	let actions = lin_kernighan(&input, &[plant([0, 1]), plant([4, 4]), plant([2, 0]), plant([1, 5])], 3);
+
+	assert_eq!(actions, vec![plant([2, 0]), plant([0, 1]), plant([1, 5]), plant([4, 4])]);
+	assert_eq!(score(&input, &actions), (4, -7));
+}
+
+#[test]
+fn improvements_keep_the_seed_stock()
+{
+	check_cases("improvements_keep_the_seed_stock", 100, |rng| random_input(rng, 10, 5), |input|
+	{
+		let initial = nearest_neighbour(input);
+		let actions = lin_kernighan(input, &initial, 4);
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("lin_kernighan gave {actions:?}"));
+		}
+		if score(input, &actions) < score(input, &initial)
+		{
+			return Err(format!("{actions:?} is worse than {initial:?}"));
+		}
+
+		Ok(())
+	});
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::metric::{self, Metric};
+use codingup_qualifs::realize::{realize, TieBreak};
+use codingup_qualifs::io::Input;
+use codingup_qualifs::{pruning, resolve, resolve_fast, resolve_q_fast, unresolve};
+use common::{check, is_valid_plan, plant, PlanCase};
+
+
+const OTHER_METRICS: [Metric; 2] = [Metric::Chebyshev, Metric::Euclidean];
+
+
+#[test]
+fn distances()
+{
+	assert_eq!(Metric::Manhattan.distance([0, 0], [3, -4]), 7);
+	assert_eq!(Metric::Chebyshev.distance([0, 0], [3, -4]), 4);
+	assert_eq!(Metric::Euclidean.distance([0, 0], [3, -4]), 5);
+	assert_eq!(Metric::Euclidean.distance([0, 0], [1, 1]), 1);
+	assert_eq!(Metric::Euclidean.distance([0, 0], [2, 2]), 3);
+}
+
+#[test]
+fn nearest_neighbour_follows_the_metric()
+{
+	// [4, 3] is the nearest with Chebyshev and Euclidean, [0, 6] with Manhattan
+	let mut input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		plants: vec![[4, 3], [0, 6]],
+		..Default::default()
+	};
+
+	assert_eq!(nearest_neighbour(&input), vec![plant([0, 6])]);
+
+	for metric in OTHER_METRICS
+	{
+		input.metric = metric;
+		assert_eq!(nearest_neighbour(&input), vec![plant([4, 3])], "{metric:?}");
+	}
+}
+
+#[test]
+fn generic_resolver_matches_manhattan()
+{
+	check("generic_resolver_matches_manhattan", PlanCase::random, |case|
+	{
+		let (_, plant_count, distance_traveled) = resolve(&case.input, &case.actions);
+		let (_, generic_plant_count, generic_distance_traveled) = metric::resolve(&case.input, &case.actions, true);
+
+		if (plant_count, distance_traveled) != (generic_plant_count, generic_distance_traveled)
+		{
+			return Err(format!("resolve {:?}, generic {:?}", (plant_count, distance_traveled), (generic_plant_count, generic_distance_traveled)));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn resolvers_agree_with_other_metrics()
+{
+	check("resolvers_agree_with_other_metrics", PlanCase::random, |case|
+	{
+		for metric in OTHER_METRICS
+		{
+			let mut input = case.input.clone();
+			input.metric = metric;
+
+			let (_, plant_count, distance_traveled) = resolve(&input, &case.actions);
+			let fast = resolve_fast(&input, &case.actions, true);
+
+			if fast != (plant_count, distance_traveled)
+			{
+				return Err(format!("{metric:?}: resolve {:?}, resolve_fast {fast:?}", (plant_count, distance_traveled)));
+			}
+
+			// The position sets only keep the positions reached with the minimum distance, never better than the exact resolution
+			let exact = resolve_fast(&input, &case.actions, false);
+			let q_fast = resolve_q_fast(&input, &case.actions, false);
+
+			if q_fast.0 != exact.0 || q_fast.1 < exact.1
+			{
+				return Err(format!("{metric:?}: resolve_fast {exact:?}, resolve_q_fast {q_fast:?} without distance limit"));
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn realization_matches_resolve_with_other_metrics()
+{
+	check("realization_matches_resolve_with_other_metrics", PlanCase::random, |case|
+	{
+		for metric in OTHER_METRICS
+		{
+			let mut input = case.input.clone();
+			input.metric = metric;
+
+			let (_, plant_count, distance_traveled) = resolve(&input, &case.actions);
+			let realization = realize(&input, &case.actions);
+
+			if (realization.plant_count, realization.distance_traveled) != (plant_count, distance_traveled)
+			{
+				return Err(format!("{metric:?}: realize {:?}, resolve {:?}", (realization.plant_count, realization.distance_traveled), (plant_count, distance_traveled)));
+			}
+
+			let moves = realization.pick(&input, TieBreak::NextSeed);
+
+			if unresolve(&input, &moves) != realization.actions
+			{
+				return Err(format!("{metric:?}: picked {moves:?}"));
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn solvers_plant_everything_with_other_metrics()
+{
+	check("solvers_plant_everything_with_other_metrics", PlanCase::random, |case|
+	{
+		for metric in OTHER_METRICS
+		{
+			let mut input = case.input.clone();
+			input.metric = metric;
+			input.plants.truncate(6);
+			input.seed_capacity = input.plants.len() as u32;
+			input.max_distance = 1000;
+
+			for (name, actions) in [("nearest_neighbour", nearest_neighbour(&input)), ("pruning", pruning::solve(&input, 1000))]
+			{
+				let (_, plant_count, _) = resolve(&input, &actions);
+
+				if !is_valid_plan(&input, &actions) || plant_count != input.plants.len()
+				{
+					return Err(format!("{metric:?}: {name} planted {plant_count} of {} with {actions:?}", input.plants.len()));
+				}
+			}
+		}
+
+		Ok(())
+	});
+}
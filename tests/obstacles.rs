@@ -0,0 +1,157 @@
+mod common;
+
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::{Input, OutAction};
+use codingup_qualifs::metric::Metric;
+use codingup_qualifs::obstacles::Obstacle;
+use codingup_qualifs::pruning;
+use codingup_qualifs::realize::{realize, TieBreak};
+use codingup_qualifs::{resolve, resolve_fast, resolve_q_fast, Action, ActionKind};
+use common::{check, check_cases, plant, random_input, PlanCase};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+
+#[test]
+fn far_obstacle_does_not_change_resolution()
+{
+	check("far_obstacle_does_not_change_resolution", PlanCase::random, |case|
+	{
+		let mut input = case.input.clone();
+		input.obstacles = vec![Obstacle::Cell([-100, -100])];
+
+		let (_, plant_count, distance_traveled) = resolve(&case.input, &case.actions);
+		let (_, obstacle_plant_count, obstacle_distance_traveled) = resolve(&input, &case.actions);
+
+		if (plant_count, distance_traveled) != (obstacle_plant_count, obstacle_distance_traveled)
+		{
+			return Err(format!("{:?} without obstacle, {:?} with", (plant_count, distance_traveled), (obstacle_plant_count, obstacle_distance_traveled)));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn cached_distances_match_the_search()
+{
+	let generate = |rng: &mut StdRng|
+	{
+		let mut input = random_input(rng, 6, 3);
+		let x = rng.random_range(1..=8);
+		input.obstacles = vec![Obstacle::Rect { min: [x, -1], max: [x, rng.random_range(0..=12)] }, Obstacle::Cell([x + 1, 2])];
+		input.metric = [Metric::Manhattan, Metric::Chebyshev][rng.random_range(0..2)];
+		input
+	};
+
+	check_cases("cached_distances_match_the_search", 50, generate, |input|
+	{
+		// The shrunk inputs are indexed too
+		let searched = Input { path_cache: None, ..input.clone() };
+		let mut indexed = input.clone();
+		indexed.index();
+
+		// The points of interest, a cell next to them and one outside of the fields
+		let points: Vec<[i32; 2]> = [input.start].iter().chain(&input.plants).chain(&input.seeds)
+			.flat_map(|&pos| [pos, [pos[0] + 1, pos[1]]])
+			.chain([[-2, 30]])
+			.collect();
+
+		for &a in &points
+		{
+			for &b in &points
+			{
+				let (dist, cached_dist) = (searched.travel_distance(a, b), indexed.travel_distance(a, b));
+
+				if dist != cached_dist
+				{
+					return Err(format!("{a:?} to {b:?}: {dist} searched, {cached_dist} cached"));
+				}
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn wall_forces_a_detour()
+{
+	let input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 100,
+		"seedCapacity": 2,
+		"range": 1,
+		"seeds": [],
+		"plants": [[6, 0]],
+		"obstacles": [{ "min": [3, -4], "max": [3, 4] }, [4, 0]]
+	}"#).unwrap();
+
+	let actions = [Action { pos: [6, 0], kind: ActionKind::Plant }];
+
+	let (moves, plant_count, distance_traveled) = resolve(&input, &actions);
+
+	// Up to y = 5 to pass the wall, across to x = 6, then down to [6, 1] next to the plant
+	assert_eq!(plant_count, 1);
+	assert_eq!(distance_traveled, 5 + 6 + 4);
+	assert_eq!(resolve_fast(&input, &actions, true), (plant_count, distance_traveled));
+	assert_eq!(resolve_q_fast(&input, &actions, true), (plant_count, distance_traveled));
+
+	// Each move is a straight line that does not cross the wall
+	let mut pos = [0, 0];
+	for action in moves
+	{
+		if let OutAction::Move(new_pos) = action
+		{
+			assert!(new_pos[0] == pos[0] || new_pos[1] == pos[1]);
+			assert!(!(pos[0].min(new_pos[0])..=pos[0].max(new_pos[0])).contains(&3) || new_pos[1].abs() > 4);
+			pos = new_pos;
+		}
+	}
+
+	assert_eq!(input.travel_distance([0, 0], [6, 0]), 5 + 6 + 5);
+}
+
+#[test]
+fn greedy_follows_travel_distance()
+{
+	let input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 100,
+		"seedCapacity": 2,
+		"range": 0,
+		"seeds": [],
+		"plants": [[2, 0], [0, 3]],
+		"obstacles": [{ "min": [1, -5], "max": [1, 5] }]
+	}"#).unwrap();
+
+	// [2, 0] is the closest plant without the wall
+	assert_eq!(nearest_neighbour(&input), vec![plant([0, 3]), plant([2, 0])]);
+}
+
+#[test]
+fn realization_and_pruning_go_around_the_wall()
+{
+	let input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 100,
+		"seedCapacity": 2,
+		"range": 1,
+		"seeds": [],
+		"plants": [[6, 0]],
+		"obstacles": [{ "min": [3, -4], "max": [3, 4] }, [4, 0]]
+	}"#).unwrap();
+
+	let actions = [Action { pos: [6, 0], kind: ActionKind::Plant }];
+
+	let realization = realize(&input, &actions);
+	assert_eq!(realization.distance_traveled, 5 + 6 + 4);
+	// Around either end of the wall, to any free cell of the range but the plant itself
+	assert_eq!(realization.positions, vec![vec![([5, 0], 15), ([6, -1], 15), ([6, 1], 15)]]);
+
+	let moves = realization.pick(&input, TieBreak::Lexicographic);
+	assert_eq!(moves.last(), Some(&OutAction::Plant([6, 0])));
+	assert!(moves.iter().all(|action| !matches!(action, OutAction::Move([3, y]) if y.abs() <= 4)));
+
+	assert_eq!(pruning::solve(&input, input.max_distance as i32), actions);
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use std::time::Duration;
+
+use codingup_qualifs::io::Input;
+use codingup_qualifs::metric::Metric;
+use codingup_qualifs::online::{rolling_horizon, simulate, Batch, Observation, Policy, Scenario};
+use codingup_qualifs::{resolve_fast, Action, ActionKind};
+use common::{check_cases, is_valid_plan, random_input};
+
+
+// Plants the first visible plant, collects the first seed when out of seeds, and remembers what it saw
+#[derive(Default)]
+struct FirstVisible
+{
+	seen: Vec<(i32, Vec<[i32; 2]>)>,
+}
+
+impl Policy for FirstVisible
+{
+	fn next_action(&mut self, _input: &Input, observation: &Observation) -> Option<Action>
+	{
+		self.seen.push((observation.distance_traveled, observation.plants.to_vec()));
+
+		if observation.seed_storage == 0
+		{
+			return observation.seeds.first().map(|&pos| Action { pos, kind: ActionKind::Collect });
+		}
+
+		observation.plants.first().map(|&pos| Action { pos, kind: ActionKind::Plant })
+	}
+}
+
+// Plants a plant of the scenario before it is revealed
+struct Cheater;
+
+impl Policy for Cheater
+{
+	fn next_action(&mut self, _input: &Input, _observation: &Observation) -> Option<Action>
+	{
+		Some(Action { pos: [9, 9], kind: ActionKind::Plant })
+	}
+}
+
+fn scenario() -> Scenario
+{
+	Scenario
+	{
+		batches: vec![
+			Batch { after: 5, plants: vec![[9, 9]] },
+			Batch { after: 2, plants: vec![[0, 4]] },
+		],
+	}
+}
+
+fn input() -> Input
+{
+	Input
+	{
+		max_distance: 100,
+		seed_capacity: 2,
+		range: 0,
+		seeds: vec![[1, 0]],
+		plants: vec![[0, 2]],
+		..Default::default()
+	}
+}
+
+
+#[test]
+fn scenario_is_parsed()
+{
+	let scenario: Scenario = serde_json::from_str(r#"{"batches": [{"after": 10, "plants": [[1, 2], [3, 4]]}]}"#).unwrap();
+
+	assert_eq!(scenario.batches[0].after, 10);
+	assert_eq!(scenario.full_input(&input()).plants, vec![[0, 2], [1, 2], [3, 4]]);
+}
+
+#[test]
+fn plants_are_revealed_after_their_distance()
+{
+	let mut policy = FirstVisible::default();
+	let actions = simulate(&input(), &scenario(), &mut policy);
+
+	assert_eq!(actions, vec![
+		Action { pos: [0, 2], kind: ActionKind::Plant },
+		Action { pos: [0, 4], kind: ActionKind::Plant },
+		Action { pos: [1, 0], kind: ActionKind::Collect },
+		Action { pos: [9, 9], kind: ActionKind::Plant },
+	]);
+
+	let visible: Vec<_> = policy.seen.iter().map(|(distance_traveled, plants)| (*distance_traveled, plants.len())).collect();
+	assert_eq!(visible, vec![(0, 1), (2, 1), (4, 0), (9, 1), (26, 0)]);
+}
+
+#[test]
+#[should_panic(expected = "has not seen")]
+fn unseen_plants_cannot_be_planted()
+{
+	simulate(&input(), &scenario(), &mut Cheater);
+}
+
+#[test]
+fn rolling_horizon_builds_valid_plans()
+{
+	check_cases("rolling_horizon_builds_valid_plans", 20, |rng| random_input(rng, 6, 3), |input|
+	{
+		// The last plants of the input are only revealed after some distance
+		let (known, revealed) = input.plants.split_at(input.plants.len() / 2);
+		let scenario = Scenario { batches: vec![Batch { after: 10, plants: revealed.to_vec() }] };
+		let online_input = Input { plants: known.to_vec(), ..input.clone() };
+
+		let actions = rolling_horizon(&online_input, &scenario, Duration::from_millis(2));
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("rolling_horizon gave {actions:?}"));
+		}
+
+		// Like pruning_time_limit, the policy keeps planting past the distance budget
+		let plants = actions.iter().filter(|action| action.kind == ActionKind::Plant).count();
+		let (plant_count, _) = resolve_fast(input, &actions, false);
+		if plant_count != plants
+		{
+			return Err(format!("resolve_fast plants {plant_count} of {actions:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn rolling_horizon_follows_the_metric_and_the_obstacles()
+{
+	let mut input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 100,
+		"seedCapacity": 1,
+		"range": 0,
+		"seeds": [[4, 4]],
+		"plants": [[2, 2], [6, 0]],
+		"obstacles": [{ "min": [3, -4], "max": [3, 3] }]
+	}"#).unwrap();
+
+	// The wall makes [6, 0] far from [2, 2], the seed is on the way around it
+	let actions = rolling_horizon(&input, &Scenario::default(), Duration::from_millis(50));
+	assert_eq!(actions, vec![
+		Action { pos: [2, 2], kind: ActionKind::Plant },
+		Action { pos: [4, 4], kind: ActionKind::Collect },
+		Action { pos: [6, 0], kind: ActionKind::Plant },
+	]);
+	assert_eq!(resolve_fast(&input, &actions, true), (2, 4 + 4 + 6));
+
+	input.obstacles.clear();
+	input.metric = Metric::Chebyshev;
+
+	let actions = rolling_horizon(&input, &Scenario::default(), Duration::from_millis(50));
+	assert_eq!(resolve_fast(&input, &actions, true), (2, 2 + 2 + 4));
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use codingup_qualifs::exact::solve_exact;
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::Input;
+use codingup_qualifs::obstacles::Obstacle;
+use codingup_qualifs::{pruning, resolve, Action};
+use common::{is_valid_plan, random_input, rng};
+
+
+const INSTANCE_COUNT: u64 = 2000;
+
+
+// Check that a solver never beats the oracle and return how many times it was optimal
+fn compare_with_oracle(name: &str, max_plants: usize, max_seeds: usize, solver: impl Fn(&Input) -> Vec<Action>) -> u64
+{
+	let mut optimal_count = 0;
+
+	for seed in 0..INSTANCE_COUNT
+	{
+		let input = random_input(&mut rng(seed), max_plants, max_seeds);
+
+		let (_, _, best_plant_count, best_distance) = solve_exact(&input);
+
+		let actions = solver(&input);
+		assert!(is_valid_plan(&input, &actions), "{name} made an invalid plan for instance {seed}");
+
+		let (_, plant_count, distance_traveled) = resolve(&input, &actions);
+
+		let value = (plant_count, -distance_traveled);
+		let best_value = (best_plant_count, -best_distance);
+
+		assert!(value <= best_value, "{name} beats the oracle on instance {seed}: {value:?} > {best_value:?}");
+
+		if value == best_value
+		{
+			optimal_count += 1;
+		}
+	}
+
+	println!("{name} is optimal on {optimal_count}/{INSTANCE_COUNT} instances");
+
+	optimal_count
+}
+
+
+#[test]
+fn exact_plan_is_valid()
+{
+	for seed in 0..INSTANCE_COUNT
+	{
+		let input = random_input(&mut rng(seed), 8, 3);
+
+		let (actions, _, plant_count, _) = solve_exact(&input);
+
+		assert!(is_valid_plan(&input, &actions));
+		assert!(plant_count <= input.plants.len());
+	}
+}
+
+#[test]
+fn exact_finds_single_plant()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 2,
+		seeds: vec![],
+		plants: vec![[5, 0]],
+		..Default::default()
+	};
+
+	let (_, _, plant_count, distance_traveled) = solve_exact(&input);
+
+	assert_eq!(plant_count, 1);
+	assert_eq!(distance_traveled, 3);
+}
+
+// Instances with their optimal plant count and distance, worked out by hand
+fn hand_built_instances() -> Vec<(Input, usize, i32)>
+{
+	let input = |max_distance, seed_capacity, range, seeds: &[[i32; 2]], plants: &[[i32; 2]]| Input
+	{
+		max_distance,
+		seed_capacity,
+		range,
+		seeds: seeds.to_vec(),
+		plants: plants.to_vec(),
+		..Default::default()
+	};
+
+	vec![
+		// Collect between the two plants
+		(input(100, 1, 0, &[[3, 0]], &[[2, 0], [4, 0]]), 2, 4),
+		// Only the nearest plant fits in the distance
+		(input(4, 1, 1, &[], &[[3, 0], [0, 5]]), 1, 2),
+		// Both plants are in range of the start
+		(input(100, 2, 2, &[], &[[2, 0], [0, 2]]), 2, 0),
+		// The far side first is shorter than the nearest plant first
+		(input(100, 3, 0, &[], &[[1, 0], [-1, 0], [5, 0]]), 3, 7),
+		// Out of seeds after one plant, the seed is a detour worth taking
+		(input(100, 1, 1, &[[0, 3]], &[[3, 0], [0, 6]]), 2, 9),
+	]
+}
+
+#[test]
+fn exact_finds_the_optimum_of_hand_built_instances()
+{
+	for (index, (input, plant_count, distance_traveled)) in hand_built_instances().into_iter().enumerate()
+	{
+		let (actions, _, exact_plant_count, exact_distance) = solve_exact(&input);
+
+		assert!(is_valid_plan(&input, &actions), "instance {index}: {actions:?}");
+		assert_eq!((exact_plant_count, exact_distance), (plant_count, distance_traveled), "instance {index}");
+	}
+}
+
+#[test]
+#[should_panic(expected = "does not support obstacles")]
+fn exact_rejects_obstacles()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		plants: vec![[2, 0]],
+		obstacles: vec![Obstacle::Rect { min: [1, -5], max: [1, 5] }],
+		..Default::default()
+	};
+
+	solve_exact(&input);
+}
+
+#[test]
+fn pruning_finds_the_optimum_of_hand_built_instances()
+{
+	// The branch and bound only looks for plans planting every plant
+	for (index, (input, plant_count, distance_traveled)) in hand_built_instances().into_iter().enumerate().filter(|(_, (input, plant_count, _))| *plant_count == input.plants.len())
+	{
+		let actions = pruning::solve(&input, input.max_distance as i32);
+		let (_, pruning_plant_count, pruning_distance) = resolve(&input, &actions);
+
+		assert_eq!((pruning_plant_count, pruning_distance), (plant_count, distance_traveled), "instance {index}");
+	}
+}
+
+#[test]
+fn nearest_neighbour_vs_oracle()
+{
+	compare_with_oracle("nearest_neighbour", 8, 3, nearest_neighbour);
+}
+
+#[test]
+fn limited_search_vs_oracle()
+{
+	compare_with_oracle("limited_search", 6, 3, |input| limited_search(input, 3));
+}
+
+#[test]
+fn pruning_vs_oracle()
+{
+	compare_with_oracle("pruning", 6, 3, |input| pruning::solve(input, input.max_distance as i32));
+}
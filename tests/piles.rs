@@ -0,0 +1,136 @@
+mod common;
+
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::Input;
+use codingup_qualifs::piles::pickup;
+use codingup_qualifs::species::feasible_prefix;
+use codingup_qualifs::{resolve, resolve_fast, resolve_q_fast, ActionKind};
+use common::{check_cases, collect, plant, random_input};
+
+
+// Piles of 1 to 3 seeds
+fn with_piles(input: &Input) -> Input
+{
+	Input
+	{
+		pile_sizes: (0..input.seeds.len() as u32).map(|index| index % 3 + 1).collect(),
+		..input.clone()
+	}
+}
+
+
+#[test]
+fn pile_sizes_are_parsed()
+{
+	let input: Input = serde_json::from_str(r#"{"maxDistance": 10, "seedCapacity": 1, "range": 0, "seeds": [[0, 1]], "plants": [[1, 2]], "pileSizes": [4]}"#).unwrap();
+
+	assert_eq!(input.pile_sizes, vec![4]);
+	assert!(input.has_piles());
+}
+
+#[test]
+fn pickup_takes_what_the_robot_can_plant()
+{
+	let input = Input { seed_capacity: 5, seeds: vec![[0, 0]], pile_sizes: vec![4], ..Default::default() };
+
+	assert_eq!(pickup(&input, 0, 4, 2), (2, 2));
+	assert_eq!(pickup(&input, 3, 4, 10), (2, 2));
+	assert_eq!(pickup(&input, 0, 4, 10), (4, 0));
+
+	// Without pile sizes the robot fills up and the pile stays as it is
+	let input = Input { pile_sizes: Vec::new(), ..input };
+	assert_eq!(pickup(&input, 3, 4, 1), (2, 4));
+}
+
+#[test]
+fn resolvers_stop_when_the_pile_is_empty()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 2,
+		range: 0,
+		seeds: vec![[1, 0]],
+		pile_sizes: vec![1],
+		plants: vec![[2, 0], [3, 0], [4, 0], [5, 0]],
+		..Default::default()
+	};
+
+	let actions = [plant([2, 0]), plant([3, 0]), collect([1, 0]), plant([4, 0]), plant([5, 0])];
+
+	assert_eq!(feasible_prefix(&input, &actions), 4);
+
+	let (_, plant_count, distance_traveled) = resolve(&input, &actions);
+	assert_eq!((plant_count, distance_traveled), (3, 8));
+	assert_eq!(resolve_fast(&input, &actions, true), (3, 8));
+	assert_eq!(resolve_q_fast(&input, &actions, true), (3, 8));
+
+	// Without pile sizes the same plan plants everything
+	let input = Input { pile_sizes: Vec::new(), ..input };
+	assert_eq!(feasible_prefix(&input, &actions), actions.len());
+}
+
+#[test]
+fn nearest_neighbour_comes_back_to_a_pile()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[0, 1]],
+		pile_sizes: vec![3],
+		plants: vec![[0, 2], [0, 3], [0, 4]],
+		..Default::default()
+	};
+
+	let actions = nearest_neighbour(&input);
+
+	assert_eq!(actions, vec![plant([0, 2]), collect([0, 1]), plant([0, 3]), collect([0, 1]), plant([0, 4])]);
+	assert_eq!(resolve_fast(&input, &actions, true), (3, 10));
+}
+
+#[test]
+fn constructors_build_feasible_plans()
+{
+	check_cases("constructors_build_feasible_plans", 300, |rng| with_piles(&random_input(rng, 8, 4)), |input|
+	{
+		// The same piles with 2 species, the even plants only accept species 0
+		let species_input = Input
+		{
+			species: (0..input.seeds.len() as u32).map(|index| index % 2).collect(),
+			requirements: (0..input.plants.len()).map(|index| if index % 2 == 0 { vec![0] } else { Vec::new() }).collect(),
+			..input.clone()
+		};
+
+		for (name, input) in [("piles", input), ("piles and species", &species_input)]
+		{
+			for (name, actions) in [(format!("{name}: nearest_neighbour"), nearest_neighbour(input)), (format!("{name}: limited_search"), limited_search(input, 3))]
+			{
+				for action in actions.iter().filter(|action| action.kind == ActionKind::Plant)
+				{
+					let planted = actions.iter().filter(|other| *other == action).count();
+					let available = input.plants.iter().filter(|&&plant| plant == action.pos).count();
+					if planted > available
+					{
+						return Err(format!("{name} plants twice in {actions:?}"));
+					}
+				}
+
+				if feasible_prefix(input, &actions) != actions.len()
+				{
+					return Err(format!("{name} gave {actions:?}"));
+				}
+
+				let (_, plant_count, distance_traveled) = resolve(input, &actions);
+				let (fast, q_fast) = (resolve_fast(input, &actions, true), resolve_q_fast(input, &actions, true));
+				if fast != (plant_count, distance_traveled) || q_fast != fast
+				{
+					return Err(format!("{name}: resolve {:?}, resolve_fast {fast:?}, resolve_q_fast {q_fast:?}", (plant_count, distance_traveled)));
+				}
+			}
+		}
+
+		Ok(())
+	});
+}
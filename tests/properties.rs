@@ -0,0 +1,215 @@
+mod common;
+
+use codingup_qualifs::io::{format_output, parse_output, Input, OutAction};
+use codingup_qualifs::quantum::QPos;
+use codingup_qualifs::realize::{realize, TieBreak};
+use codingup_qualifs::{distance, resolve, resolve_fast, resolve_q_fast, unresolve, ActionKind};
+use common::{check, shrink_value, PlanCase, Shrink};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+
+#[derive(Debug, Clone)]
+struct QPosCase
+{
+	pos: QPos,
+	plant: [i32; 2],
+	range: i32,
+}
+
+impl QPosCase
+{
+	fn random(rng: &mut StdRng) -> Self
+	{
+		QPosCase
+		{
+			pos: QPos
+			{
+				robot_pos: [rng.random_range(-20..=20), rng.random_range(-20..=20)],
+				range: rng.random_range(-8..=8),
+			},
+			plant: [rng.random_range(-30..=30), rng.random_range(-30..=30)],
+			range: rng.random_range(0..=8),
+		}
+	}
+
+	fn input(&self) -> Input
+	{
+		Input
+		{
+			max_distance: 0,
+			seed_capacity: 1,
+			range: self.range,
+			seeds: Vec::new(),
+			plants: vec![self.plant],
+			..Default::default()
+		}
+	}
+
+	// Cells of the diagonal segment represented by the QPos
+	fn cells(&self) -> Vec<[i32; 2]>
+	{
+		let sign = if self.pos.range < 0 { -1 } else { 1 };
+		(0..=self.pos.range.abs())
+			.map(|t| [self.pos.robot_pos[0] + sign * t, self.pos.robot_pos[1] + t])
+			.collect()
+	}
+}
+
+impl Shrink for QPosCase
+{
+	fn shrink(&self) -> Vec<Self>
+	{
+		let fields: [fn(&mut QPosCase) -> &mut i32; 6] = [
+			|case| &mut case.pos.robot_pos[0],
+			|case| &mut case.pos.robot_pos[1],
+			|case| &mut case.pos.range,
+			|case| &mut case.plant[0],
+			|case| &mut case.plant[1],
+			|case| &mut case.range,
+		];
+
+		let mut res = Vec::new();
+
+		for field in fields
+		{
+			for value in shrink_value(*field(&mut self.clone()))
+			{
+				let mut case = self.clone();
+				*field(&mut case) = value;
+				res.push(case);
+			}
+		}
+
+		res
+	}
+}
+
+
+#[test]
+fn resolvers_agree()
+{
+	check("resolvers_agree", PlanCase::random, |case|
+	{
+		let (_, plant_count, distance_traveled) = resolve(&case.input, &case.actions);
+		let fast = resolve_fast(&case.input, &case.actions, true);
+		let q_fast = resolve_q_fast(&case.input, &case.actions, true);
+
+		if fast != (plant_count, distance_traveled) || q_fast != fast
+		{
+			return Err(format!("resolve {:?}, resolve_fast {fast:?}, resolve_q_fast {q_fast:?}", (plant_count, distance_traveled)));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn apply_plant_cost_is_not_underestimated()
+{
+	check("apply_plant_cost_is_not_underestimated", QPosCase::random, |case|
+	{
+		let (_, cost) = case.pos.apply_plant(&case.input(), case.plant);
+
+		let min_cost = case.cells().into_iter()
+			.map(|cell| (distance(cell, case.plant) - case.range).max(0))
+			.min()
+			.unwrap();
+
+		if cost < min_cost
+		{
+			return Err(format!("apply_plant cost {cost} is lower than the minimum {min_cost}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn unresolve_round_trip()
+{
+	check("unresolve_round_trip", PlanCase::random, |case|
+	{
+		let (mut out_actions, plant_count, _) = resolve(&case.input, &case.actions);
+
+		// resolve keeps the actions up to the last plant it could reach
+		let end = case.actions.iter()
+			.enumerate()
+			.filter(|(_, action)| action.kind == ActionKind::Plant)
+			.nth(plant_count.wrapping_sub(1))
+			.map_or(0, |(index, _)| index + 1);
+
+		let actions = unresolve(&case.input, out_actions.make_contiguous());
+
+		if actions != case.actions[..end]
+		{
+			return Err(format!("unresolve gave {actions:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn output_json_round_trip()
+{
+	check("output_json_round_trip", PlanCase::random, |case|
+	{
+		let (mut out_actions, _, _) = resolve(&case.input, &case.actions);
+		let out_actions = out_actions.make_contiguous();
+
+		let json = serde_json::to_string_pretty(&format_output(out_actions)).unwrap();
+		let moves_str: Vec<String> = serde_json::from_str(&json).map_err(|err| err.to_string())?;
+		let parsed = parse_output(&moves_str);
+
+		if parsed != out_actions
+		{
+			return Err(format!("parsed {parsed:?} from {json}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn realization_is_optimal()
+{
+	check("realization_is_optimal", PlanCase::random, |case|
+	{
+		let (_, plant_count, distance_traveled) = resolve(&case.input, &case.actions);
+		let realization = realize(&case.input, &case.actions);
+
+		if (realization.plant_count, realization.distance_traveled) != (plant_count, distance_traveled)
+		{
+			return Err(format!("realize {:?}, resolve {:?}", (realization.plant_count, realization.distance_traveled), (plant_count, distance_traveled)));
+		}
+
+		for tie_break in [TieBreak::Lexicographic, TieBreak::NextSeed]
+		{
+			let moves = realization.pick(&case.input, tie_break);
+
+			let mut pos = case.input.start;
+			let mut total = 0;
+			for action in &moves
+			{
+				match action
+				{
+					OutAction::Move(new_pos) =>
+					{
+						total += distance(pos, *new_pos);
+						pos = *new_pos;
+					},
+					OutAction::Plant(plant) if distance(pos, *plant) > case.input.range => return Err(format!("{plant:?} out of range in {moves:?}")),
+					_ => (),
+				}
+			}
+
+			if total != distance_traveled || unresolve(&case.input, &moves) != realization.actions
+			{
+				return Err(format!("{tie_break:?} gave {moves:?} with distance {total}"));
+			}
+		}
+
+		Ok(())
+	});
+}
@@ -0,0 +1,230 @@
+use codingup_qualifs::cell_set::CellSet;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::quantum::{QPos, QRect};
+
+
+const MAX_OFFSET: i32 = 12;
+const MAX_RANGE: i32 = 6;
+
+
+fn input(range: i32) -> Input
+{
+	Input
+	{
+		max_distance: 0,
+		seed_capacity: 1,
+		range,
+		seeds: Vec::new(),
+		plants: Vec::new(),
+		..Default::default()
+	}
+}
+
+fn report(mismatches: &[String])
+{
+	for mismatch in mismatches.iter().take(20)
+	{
+		println!("{mismatch}");
+	}
+
+	assert!(mismatches.is_empty(), "{} mismatches", mismatches.len());
+}
+
+
+// A QPos segment always comes from the edge of a plant diamond, so its length never exceeds the range
+#[test]
+fn apply_plant_matches_reference()
+{
+	let mut mismatches = Vec::new();
+
+	for range in 0..=MAX_RANGE
+	{
+		let input = input(range);
+
+		for segment in -range..=range
+		{
+			let pos = QPos { robot_pos: [0, 0], range: segment };
+
+			for dx in -MAX_OFFSET..=MAX_OFFSET
+			{
+				for dy in -MAX_OFFSET..=MAX_OFFSET
+				{
+					let (new_pos, dist) = pos.apply_plant(&input, [dx, dy]);
+					let (ref_cells, ref_dist) = CellSet::from(pos).apply_plant(&input, [dx, dy]);
+
+					if dist != ref_dist
+					{
+						mismatches.push(format!("{pos:?} plant [{dx}, {dy}] range {range}: cost {dist}, expected {ref_dist}"));
+					}
+					else if CellSet::from(new_pos) != ref_cells
+					{
+						mismatches.push(format!("{pos:?} plant [{dx}, {dy}] range {range}: cells {:?}, expected {:?}", CellSet::from(new_pos).cells, ref_cells.cells));
+					}
+				}
+			}
+		}
+	}
+
+	report(&mismatches);
+}
+
+#[test]
+fn apply_seed_matches_reference()
+{
+	let mut mismatches = Vec::new();
+
+	for segment in -MAX_RANGE..=MAX_RANGE
+	{
+		let pos = QPos { robot_pos: [0, 0], range: segment };
+
+		for dx in -MAX_OFFSET..=MAX_OFFSET
+		{
+			for dy in -MAX_OFFSET..=MAX_OFFSET
+			{
+				let (new_pos, dist) = pos.apply_seed([dx, dy]);
+				let (ref_cells, ref_dist) = CellSet::from(pos).apply_seed(&input(0), [dx, dy]);
+
+				if dist != ref_dist || CellSet::from(new_pos) != ref_cells
+				{
+					mismatches.push(format!("{pos:?} seed [{dx}, {dy}]: {new_pos:?} cost {dist}, expected cost {ref_dist}"));
+				}
+			}
+		}
+	}
+
+	report(&mismatches);
+}
+
+// Two plants in a row starting from a single cell, the only way to reach most segments in practice
+#[test]
+fn plant_sequences_match_reference()
+{
+	let mut mismatches = Vec::new();
+
+	for range in 0..=MAX_RANGE
+	{
+		let input = input(range);
+
+		for first in (-MAX_OFFSET..=MAX_OFFSET).flat_map(|dx| (-MAX_OFFSET..=MAX_OFFSET).map(move |dy| [dx, dy]))
+		{
+			let (pos, _) = QPos::default().apply_plant(&input, first);
+			let (ref_cells, _) = CellSet::from(QPos::default()).apply_plant(&input, first);
+
+			for dx in (-MAX_OFFSET..=MAX_OFFSET).step_by(2)
+			{
+				for dy in (-MAX_OFFSET..=MAX_OFFSET).step_by(2)
+				{
+					let (new_pos, dist) = pos.apply_plant(&input, [dx, dy]);
+					let (new_ref_cells, ref_dist) = ref_cells.apply_plant(&input, [dx, dy]);
+
+					if dist != ref_dist || CellSet::from(new_pos) != new_ref_cells
+					{
+						mismatches.push(format!("plants {first:?} [{dx}, {dy}] range {range}: {new_pos:?} cost {dist}, expected cost {ref_dist}"));
+					}
+				}
+			}
+		}
+	}
+
+	report(&mismatches);
+}
+
+fn rects(max_size: i32) -> impl Iterator<Item = QRect>
+{
+	(0..=max_size).flat_map(move |u| (0..=max_size).flat_map(move |v| (0..=1).map(move |parity|
+	{
+		QRect::new([parity, 0], [parity + u, v])
+	})))
+	.filter(|rect| !rect.is_empty())
+}
+
+// Smallest rectangle containing the cells
+fn bounds(cells: &CellSet) -> QRect
+{
+	let rotated: Vec<[i32; 2]> = cells.cells.iter().map(|cell| [cell[0] + cell[1], cell[0] - cell[1]]).collect();
+
+	QRect
+	{
+		min: [rotated.iter().map(|pos| pos[0]).min().unwrap(), rotated.iter().map(|pos| pos[1]).min().unwrap()],
+		max: [rotated.iter().map(|pos| pos[0]).max().unwrap(), rotated.iter().map(|pos| pos[1]).max().unwrap()],
+	}
+}
+
+#[test]
+fn qrect_matches_qpos()
+{
+	for segment in -MAX_RANGE..=MAX_RANGE
+	{
+		let pos = QPos { robot_pos: [3, -2], range: segment };
+
+		assert_eq!(CellSet::from(QRect::from(pos)), CellSet::from(pos), "{pos:?}");
+	}
+}
+
+// Unlike QPos, QRect must be exact for every shape, even those that cannot be reached from a single cell
+#[test]
+fn qrect_apply_plant_matches_reference()
+{
+	let mut mismatches = Vec::new();
+
+	for range in 0..=MAX_RANGE
+	{
+		let input = input(range);
+
+		for rect in rects(MAX_RANGE)
+		{
+			let cells = CellSet::from(rect);
+
+			for dx in -MAX_OFFSET..=MAX_OFFSET
+			{
+				for dy in -MAX_OFFSET..=MAX_OFFSET
+				{
+					let (new_rect, dist) = rect.apply_plant(&input, [dx, dy]);
+					let (ref_cells, ref_dist) = cells.apply_plant(&input, [dx, dy]);
+
+					if dist != ref_dist
+					{
+						mismatches.push(format!("{rect:?} plant [{dx}, {dy}] range {range}: cost {dist}, expected {ref_dist}"));
+					}
+					else if CellSet::from(new_rect) != ref_cells
+					{
+						mismatches.push(format!("{rect:?} plant [{dx}, {dy}] range {range}: cells {:?}, expected {:?}", CellSet::from(new_rect).cells, ref_cells.cells));
+					}
+					else if new_rect != bounds(&ref_cells)
+					{
+						mismatches.push(format!("{rect:?} plant [{dx}, {dy}] range {range}: {new_rect:?} is not normalized"));
+					}
+				}
+			}
+		}
+	}
+
+	report(&mismatches);
+}
+
+#[test]
+fn qrect_apply_seed_matches_reference()
+{
+	let mut mismatches = Vec::new();
+
+	for rect in rects(MAX_RANGE)
+	{
+		let cells = CellSet::from(rect);
+
+		for dx in -MAX_OFFSET..=MAX_OFFSET
+		{
+			for dy in -MAX_OFFSET..=MAX_OFFSET
+			{
+				let (new_rect, dist) = rect.apply_seed([dx, dy]);
+				let (ref_cells, ref_dist) = cells.apply_seed(&input(0), [dx, dy]);
+
+				if dist != ref_dist || CellSet::from(new_rect) != ref_cells
+				{
+					mismatches.push(format!("{rect:?} seed [{dx}, {dy}]: {new_rect:?} cost {dist}, expected cost {ref_dist}"));
+				}
+			}
+		}
+	}
+
+	report(&mismatches);
+}
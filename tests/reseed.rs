@@ -0,0 +1,141 @@
+mod common;
+
+use codingup_qualifs::exact::solve_exact;
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::reseed::{insert_seeds, insert_seeds_near, plant_order, reseed};
+use codingup_qualifs::resolve_fast;
+use common::{check_cases, collect, is_valid_plan, plant, random_input};
+
+
+#[test]
+fn seeds_go_where_they_cost_the_least()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 2,
+		range: 0,
+		seeds: vec![[0, 20], [5, 1], [9, 0]],
+		plants: vec![[2, 0], [4, 0], [6, 0], [8, 0]],
+		..Default::default()
+	};
+
+	let (actions, distance_traveled) = insert_seeds(&input, &input.plants).unwrap();
+
+	assert_eq!(actions, vec![plant([2, 0]), plant([4, 0]), collect([5, 1]), plant([6, 0]), plant([8, 0])]);
+	assert_eq!(distance_traveled, 10);
+	assert_eq!(resolve_fast(&input, &actions, true), (4, 10));
+
+	// With the return leg the pile on the way back is better
+	let input = Input { return_to_base: true, seeds: vec![[0, 20], [9, 0], [3, 1]], plants: vec![[6, 0], [8, 0], [4, 0], [2, 0]], ..input };
+	let (actions, distance_traveled) = insert_seeds(&input, &input.plants).unwrap();
+
+	assert_eq!(actions, vec![plant([6, 0]), plant([8, 0]), collect([9, 0]), plant([4, 0]), plant([2, 0])]);
+	assert_eq!(distance_traveled, 18);
+}
+
+#[test]
+fn missing_seeds_give_none()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[1, 0]],
+		plants: vec![[2, 0], [3, 0], [4, 0]],
+		..Default::default()
+	};
+
+	assert!(insert_seeds(&input, &input.plants).is_none());
+	assert!(insert_seeds(&input, &input.plants[..2]).is_some());
+}
+
+#[test]
+fn states_keep_the_piles_they_used()
+{
+	// Collecting [0, 2] first is shorter but leaves the far pile for the last plant
+	let input = Input
+	{
+		max_distance: 4,
+		seed_capacity: 1,
+		range: 2,
+		seeds: vec![[1, 0], [0, 2], [0, 2]],
+		plants: vec![[0, 0]; 4],
+		..Default::default()
+	};
+
+	let (actions, distance_traveled) = insert_seeds(&input, &input.plants).unwrap();
+
+	assert_eq!(actions, vec![plant([0, 0]), collect([1, 0]), plant([0, 0]), collect([0, 2]), plant([0, 0]), collect([0, 2]), plant([0, 0])]);
+	assert_eq!(distance_traveled, 4);
+
+	// The second pile on [0, 2] is not one of the two nearest
+	assert!(insert_seeds_near(&input, &input.plants, 2).is_none());
+	assert_eq!(insert_seeds_near(&input, &input.plants, 3), Some((actions, distance_traveled)));
+}
+
+#[test]
+fn exact_plans_keep_their_distance()
+{
+	check_cases("exact_plans_keep_their_distance", 200, |rng| random_input(rng, 5, 3), |input|
+	{
+		let (exact_actions, _, plant_count, distance_traveled) = solve_exact(input);
+		let plants = plant_order(&exact_actions);
+
+		if plant_count != plants.len()
+		{
+			return Ok(());
+		}
+
+		let Some((actions, dp_distance)) = insert_seeds(input, &plants)
+		else
+		{
+			return Err(format!("insert_seeds found nothing for {plants:?}"));
+		};
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("insert_seeds gave {actions:?}"));
+		}
+		if plant_order(&actions) != plants
+		{
+			return Err(format!("insert_seeds changed the order of {plants:?} into {actions:?}"));
+		}
+		if resolve_fast(input, &actions, false) != (plant_count, dp_distance)
+		{
+			return Err(format!("resolve_fast disagrees with {dp_distance} for {actions:?}"));
+		}
+		if dp_distance > distance_traveled
+		{
+			return Err(format!("insert_seeds travels {dp_distance} for {distance_traveled}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn reseed_never_makes_a_plan_worse()
+{
+	check_cases("reseed_never_makes_a_plan_worse", 200, |rng| random_input(rng, 8, 4), |input|
+	{
+		let actions = nearest_neighbour(input);
+		let reseeded = reseed(input, &actions);
+
+		let (plant_count, distance_traveled) = resolve_fast(input, &actions, true);
+		let (new_plant_count, new_distance_traveled) = resolve_fast(input, &reseeded, true);
+
+		if !is_valid_plan(input, &reseeded)
+		{
+			return Err(format!("reseed gave {reseeded:?}"));
+		}
+		if (new_plant_count, -new_distance_traveled) < (plant_count, -distance_traveled)
+		{
+			return Err(format!("reseed scores {:?} for {:?}", (new_plant_count, new_distance_traveled), (plant_count, distance_traveled)));
+		}
+
+		Ok(())
+	});
+}
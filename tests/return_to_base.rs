@@ -0,0 +1,209 @@
+mod common;
+
+use codingup_qualifs::exact::solve_exact;
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::{Input, OutAction};
+use codingup_qualifs::metric::{self, Metric};
+use codingup_qualifs::realize::{realize, TieBreak};
+use codingup_qualifs::{pruning, resolve, resolve_fast, resolve_q_fast, Action, ActionKind};
+use common::{check, check_cases, random_input, PlanCase};
+
+
+const START: [i32; 2] = [3, 5];
+
+
+fn with_base(input: &Input) -> Input
+{
+	Input
+	{
+		start: START,
+		return_to_base: true,
+		..input.clone()
+	}
+}
+
+// Distance of the MOVEs from the start, checking every plant is in range. Returns the distance and the end position.
+fn walk(input: &Input, moves: &[OutAction]) -> Result<(i32, [i32; 2]), String>
+{
+	let mut pos = input.start;
+	let mut total = 0;
+
+	for action in moves
+	{
+		match action
+		{
+			OutAction::Move(new_pos) =>
+			{
+				total += input.travel_distance(pos, *new_pos);
+				pos = *new_pos;
+			},
+			OutAction::Plant(plant) if input.travel_distance(pos, *plant) > input.range => return Err(format!("{plant:?} out of range in {moves:?}")),
+			_ => (),
+		}
+	}
+
+	Ok((total, pos))
+}
+
+
+#[test]
+fn output_ends_at_base()
+{
+	check("output_ends_at_base", PlanCase::random, |case|
+	{
+		for metric in [Metric::Manhattan, Metric::Chebyshev]
+		{
+			let mut input = with_base(&case.input);
+			input.metric = metric;
+
+			let (mut moves, plant_count, distance_traveled) = resolve(&input, &case.actions);
+			let (total, end) = walk(&input, moves.make_contiguous())?;
+
+			if plant_count > 0 && (end != START || total != distance_traveled || total > input.max_distance as i32)
+			{
+				return Err(format!("{metric:?}: ends at {end:?} after {total}, reported {distance_traveled}, moves {moves:?}"));
+			}
+
+			let fast = resolve_fast(&input, &case.actions, true);
+			let (_, generic_plant_count, generic_distance_traveled) = metric::resolve(&input, &case.actions, true);
+
+			if fast != (plant_count, distance_traveled) || (generic_plant_count, generic_distance_traveled) != fast
+			{
+				return Err(format!("{metric:?}: resolve {:?}, resolve_fast {fast:?}, generic {:?}", (plant_count, distance_traveled), (generic_plant_count, generic_distance_traveled)));
+			}
+
+			// The position sets may miss a position closer to the base, they never find a better plan
+			let exact = resolve_fast(&input, &case.actions, false);
+			let q_fast = resolve_q_fast(&input, &case.actions, false);
+
+			if q_fast.0 != exact.0 || q_fast.1 < exact.1
+			{
+				return Err(format!("{metric:?}: resolve_fast {exact:?}, resolve_q_fast {q_fast:?} without distance limit"));
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn realization_returns_to_base()
+{
+	check("realization_returns_to_base", PlanCase::random, |case|
+	{
+		let input = with_base(&case.input);
+
+		let (_, plant_count, distance_traveled) = resolve(&input, &case.actions);
+		let realization = realize(&input, &case.actions);
+
+		if (realization.plant_count, realization.distance_traveled) != (plant_count, distance_traveled)
+		{
+			return Err(format!("realize {:?}, resolve {:?}", (realization.plant_count, realization.distance_traveled), (plant_count, distance_traveled)));
+		}
+
+		for tie_break in [TieBreak::Lexicographic, TieBreak::NextSeed]
+		{
+			let moves = realization.pick(&input, tie_break);
+			let (total, end) = walk(&input, &moves)?;
+
+			if total != distance_traveled || end != START
+			{
+				return Err(format!("{tie_break:?} gave {moves:?} with distance {total}"));
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn moving_the_start_is_a_translation()
+{
+	check("moving_the_start_is_a_translation", PlanCase::random, |case|
+	{
+		let shift = |pos: [i32; 2]| [pos[0] + START[0], pos[1] + START[1]];
+
+		let shifted = Input
+		{
+			start: START,
+			seeds: case.input.seeds.iter().copied().map(shift).collect(),
+			plants: case.input.plants.iter().copied().map(shift).collect(),
+			..case.input.clone()
+		};
+		let shifted_actions: Vec<Action> = case.actions.iter().map(|action| Action { pos: shift(action.pos), kind: action.kind }).collect();
+
+		let (_, plant_count, distance_traveled) = resolve(&case.input, &case.actions);
+		let (_, shifted_plant_count, shifted_distance_traveled) = resolve(&shifted, &shifted_actions);
+
+		if (plant_count, distance_traveled) != (shifted_plant_count, shifted_distance_traveled)
+		{
+			return Err(format!("{:?} from the origin, {:?} from {START:?}", (plant_count, distance_traveled), (shifted_plant_count, shifted_distance_traveled)));
+		}
+
+		let plan = nearest_neighbour(&shifted);
+		if plan.iter().map(|action| action.pos).ne(nearest_neighbour(&case.input).iter().map(|action| shift(action.pos)))
+		{
+			return Err(format!("nearest_neighbour gave {plan:?} from {START:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn the_greedy_keeps_the_way_back()
+{
+	let input = Input
+	{
+		max_distance: 4,
+		seed_capacity: 2,
+		range: 0,
+		plants: vec![[5, 5], [6, 5]],
+		start: START,
+		return_to_base: true,
+		..Default::default()
+	};
+
+	// Planting [6, 5] too would need 6 with the way back
+	let plan = nearest_neighbour(&input);
+	assert_eq!(plan, vec![Action { pos: [5, 5], kind: ActionKind::Plant }]);
+	assert_eq!(resolve_fast(&input, &plan, true), (1, 4));
+}
+
+#[test]
+fn solvers_respect_the_return_leg()
+{
+	check_cases("solvers_respect_the_return_leg", 300, |rng| with_base(&random_input(rng, 6, 3)), |input|
+	{
+		let (_, _, optimal_plant_count, optimal_distance) = solve_exact(input);
+
+		let plans = [
+			("nearest_neighbour", nearest_neighbour(input)),
+			("limited_search", limited_search(input, 3)),
+			("pruning", pruning::solve(input, input.max_distance as i32)),
+		];
+
+		for (name, actions) in plans
+		{
+			let (mut moves, plant_count, distance_traveled) = resolve(input, &actions);
+			let (_, end) = walk(input, moves.make_contiguous())?;
+
+			if plant_count > 0 && end != START
+			{
+				return Err(format!("{name} ends at {end:?}"));
+			}
+
+			if distance_traveled > input.max_distance as i32
+			{
+				return Err(format!("{name} is over budget with {distance_traveled}"));
+			}
+
+			if (plant_count, -distance_traveled) > (optimal_plant_count, -optimal_distance)
+			{
+				return Err(format!("{name} beats the exact solver with {:?}", (plant_count, distance_traveled)));
+			}
+		}
+
+		Ok(())
+	});
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::robust::{evaluate, replay, robust_plan};
+use codingup_qualifs::resolve_fast;
+use common::{check_cases, collect, plant, rng, random_input};
+
+
+// Two piles as close to the plants, the first one is always empty
+fn input() -> Input
+{
+	Input
+	{
+		max_distance: 6,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[1, 3], [-1, 3]],
+		failure_probabilities: vec![1.0, 0.0],
+		plants: vec![[0, 2], [0, 4]],
+		..Default::default()
+	}
+}
+
+
+#[test]
+fn failure_probabilities_are_parsed()
+{
+	let input: Input = serde_json::from_str(r#"{"maxDistance": 10, "seedCapacity": 1, "range": 0, "seeds": [[0, 1]], "plants": [], "failureProbabilities": [0.25]}"#).unwrap();
+
+	assert_eq!(input.failure_probabilities, vec![0.25]);
+}
+
+#[test]
+fn piles_fall_back_to_the_safest_spare()
+{
+	let input = Input
+	{
+		seeds: vec![[5, 0], [6, 0], [20, 0]],
+		failure_probabilities: vec![0.5, 0.9, 0.1],
+		..input()
+	};
+
+	let plan = robust_plan(&input, &[plant([0, 2]), collect([5, 0]), plant([0, 4])]);
+
+	assert_eq!(plan.fallbacks.get(&0), Some(&2));
+	assert_eq!(plan.fallbacks.get(&2), Some(&1));
+	assert_eq!(plan.fallbacks.get(&1), Some(&2));
+}
+
+#[test]
+fn replay_follows_the_fallbacks()
+{
+	let input = input();
+	let plan = robust_plan(&input, &[plant([0, 2]), collect([1, 3]), plant([0, 4])]);
+
+	assert_eq!(replay(&input, &plan, &[]), plan.actions);
+	assert_eq!(replay(&input, &plan, &[0]), vec![plant([0, 2]), collect([1, 3]), collect([-1, 3]), plant([0, 4])]);
+
+	// Without seeds left, the robot stops before the next plant
+	assert_eq!(replay(&input, &plan, &[0, 1]), vec![plant([0, 2]), collect([1, 3]), collect([-1, 3])]);
+}
+
+#[test]
+fn evaluation_tells_equal_plans_apart()
+{
+	let input = input();
+
+	let risky = robust_plan(&input, &[plant([0, 2]), collect([1, 3]), plant([0, 4])]);
+	let safe = robust_plan(&input, &[plant([0, 2]), collect([-1, 3]), plant([0, 4])]);
+
+	// Both plans plant everything with the same distance when no pile fails
+	assert_eq!(resolve_fast(&input, &risky.actions, true), resolve_fast(&input, &safe.actions, true));
+
+	let risky = evaluate(&input, &risky, 100, &mut rng(0)).unwrap();
+	let safe = evaluate(&input, &safe, 100, &mut rng(0)).unwrap();
+
+	assert_eq!((safe.expected_plants, safe.overrun_rate), (2.0, 0.0));
+	assert_eq!((risky.expected_plants, risky.overrun_rate), (1.0, 1.0));
+}
+
+#[test]
+fn evaluation_without_failures_is_the_resolver()
+{
+	check_cases("evaluation_without_failures_is_the_resolver", 100, |rng| random_input(rng, 6, 3), |input|
+	{
+		let actions = nearest_neighbour(input);
+
+		let Some(evaluation) = evaluate(input, &robust_plan(input, &actions), 5, &mut rng(0))
+		else
+		{
+			return Err("no evaluation without species".to_string());
+		};
+		let (plant_count, _) = resolve_fast(input, &actions, true);
+
+		if evaluation.expected_plants != plant_count as f64
+		{
+			return Err(format!("expected {} plants, resolve_fast plants {plant_count}", evaluation.expected_plants));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn piles_on_the_same_cell_are_collected_each()
+{
+	let input = Input
+	{
+		seed_capacity: 1,
+		seeds: vec![[0, 3], [0, 3]],
+		failure_probabilities: vec![0.0, 0.0],
+		plants: vec![[0, 1], [0, 2], [0, 4]],
+		max_distance: 10,
+		..input()
+	};
+
+	let actions = vec![plant([0, 1]), collect([0, 3]), plant([0, 2]), collect([0, 3]), plant([0, 4])];
+	let plan = robust_plan(&input, &actions);
+
+	assert_eq!(replay(&input, &plan, &[]), actions);
+	assert_eq!(evaluate(&input, &plan, 10, &mut rng(0)).unwrap().expected_plants, 3.0);
+}
+
+#[test]
+fn species_are_not_evaluated()
+{
+	let input = Input { species: vec![0, 1], ..input() };
+	let plan = robust_plan(&input, &[plant([0, 2]), collect([1, 3]), plant([0, 4])]);
+
+	assert_eq!(evaluate(&input, &plan, 10, &mut rng(0)), None);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use codingup_qualifs::io::Input;
+use codingup_qualifs::savings::savings;
+use codingup_qualifs::{resolve, resolve_fast, ActionKind};
+use common::{check_cases, collect, is_valid_plan, plant, random_input};
+
+
+#[test]
+fn trips_stay_in_their_cluster()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 2,
+		range: 0,
+		seeds: vec![[9, 0], [0, 9]],
+		plants: vec![[10, 0], [0, 10], [11, 0], [0, 11]],
+		..Default::default()
+	};
+
+	let actions = savings(&input);
+
+	assert_eq!(actions, vec![plant([10, 0]), plant([11, 0]), collect([9, 0]), plant([0, 10]), plant([0, 11])]);
+	assert_eq!(resolve_fast(&input, &actions, true), (4, 33));
+}
+
+#[test]
+fn trips_fit_the_seed_capacity()
+{
+	check_cases("trips_fit_the_seed_capacity", 300, |rng| random_input(rng, 10, 5), |input|
+	{
+		let actions = savings(input);
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("savings gave {actions:?}"));
+		}
+
+		// Every plant is planted while there are piles left for the trips
+		let trips = actions.iter().filter(|action| action.kind == ActionKind::Collect).count() + 1;
+		let plants = actions.iter().filter(|action| action.kind == ActionKind::Plant).count();
+		if plants != input.plants.len() && trips != input.seeds.len() + 1 && input.seed_capacity != 0
+		{
+			return Err(format!("savings leaves plants and piles behind in {actions:?}"));
+		}
+
+		let (_, plant_count, distance_traveled) = resolve(input, &actions);
+		if resolve_fast(input, &actions, true) != (plant_count, distance_traveled)
+		{
+			return Err(format!("resolve_fast disagrees with resolve on {actions:?}"));
+		}
+
+		Ok(())
+	});
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::Input;
+use codingup_qualifs::species::{feasible_prefix, Inventory};
+use codingup_qualifs::{resolve, resolve_fast, resolve_q_fast};
+use common::{check_cases, collect, is_valid_plan, plant, random_input};
+
+
+// Seeds of 3 species, every other plant only accepts one of them
+fn with_species(input: &Input) -> Input
+{
+	Input
+	{
+		species: (0..input.seeds.len() as u32).map(|index| index % 3).collect(),
+		requirements: (0..input.plants.len() as u32).map(|index| if index % 2 == 0 { vec![index % 3] } else { Vec::new() }).collect(),
+		..input.clone()
+	}
+}
+
+
+#[test]
+fn species_are_parsed()
+{
+	let input: Input = serde_json::from_str(r#"{"maxDistance": 10, "seedCapacity": 1, "range": 0, "seeds": [[0, 1]], "plants": [[1, 2], [3, 4]], "species": [7], "requirements": [[7, 8], []]}"#).unwrap();
+
+	assert_eq!(input.seed_species([0, 1]), Some(7));
+	assert!(input.accepts([1, 2], Some(8)));
+	assert!(!input.accepts([1, 2], Some(9)));
+	assert!(input.accepts([3, 4], Some(9)));
+	assert!(input.accepts([1, 2], None));
+}
+
+#[test]
+fn inventory_uses_the_scarcest_species_first()
+{
+	let input = Input
+	{
+		seed_capacity: 3,
+		seeds: vec![[0, 0], [1, 0]],
+		species: vec![1, 2],
+		plants: vec![[5, 0], [6, 0], [7, 0]],
+		requirements: vec![vec![1, 2], vec![1], vec![2]],
+		..Default::default()
+	};
+
+	let mut inventory = Inventory::new(&input);
+	assert!(inventory.plant(&input, [5, 0]));
+	assert!(inventory.plant(&input, [5, 0]));
+
+	// One seed without species left, the collect tops up with species 1
+	inventory.collect(&input, [0, 0]);
+	assert_eq!(inventory.total(), 3);
+
+	// Species 1 fits 2 plants, it goes before the seed without species
+	assert!(inventory.plant(&input, [5, 0]));
+	assert!(inventory.plant(&input, [6, 0]));
+	assert!(inventory.plant(&input, [7, 0]));
+	assert!(inventory.is_empty());
+
+	// The demand counted by the index is the one of the scan
+	let mut indexed = input.clone();
+	indexed.index();
+	assert_eq!(indexed.species_demand.get(&1), Some(&2));
+	assert_eq!((indexed.demand(1), indexed.demand(2)), (input.demand(1), input.demand(2)));
+}
+
+#[test]
+fn resolvers_stop_at_incompatible_plants()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[2, 0], [3, 0]],
+		species: vec![1, 2],
+		plants: vec![[1, 0], [4, 0], [5, 0]],
+		requirements: vec![Vec::new(), vec![1], vec![2]],
+		..Default::default()
+	};
+
+	let actions = [plant([1, 0]), collect([3, 0]), plant([4, 0]), collect([2, 0]), plant([5, 0])];
+
+	assert_eq!(feasible_prefix(&input, &actions), 2);
+
+	let (_, plant_count, distance_traveled) = resolve(&input, &actions);
+	assert_eq!((plant_count, distance_traveled), (1, 1));
+	assert_eq!(resolve_fast(&input, &actions, true), (1, 1));
+	assert_eq!(resolve_q_fast(&input, &actions, true), (1, 1));
+}
+
+#[test]
+fn nearest_neighbour_skips_useless_seeds()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[2, 0], [5, 0]],
+		species: vec![1, 2],
+		plants: vec![[1, 0], [10, 0]],
+		requirements: vec![Vec::new(), vec![2]],
+		..Default::default()
+	};
+
+	assert_eq!(nearest_neighbour(&input), vec![plant([1, 0]), collect([5, 0]), plant([10, 0])]);
+}
+
+#[test]
+fn constructors_build_compatible_plans()
+{
+	check_cases("constructors_build_compatible_plans", 300, |rng| with_species(&random_input(rng, 8, 4)), |input|
+	{
+		for (name, actions) in [("nearest_neighbour", nearest_neighbour(input)), ("limited_search", limited_search(input, 3))]
+		{
+			if !is_valid_plan(input, &actions) || feasible_prefix(input, &actions) != actions.len()
+			{
+				return Err(format!("{name} gave {actions:?}"));
+			}
+
+			let (_, plant_count, distance_traveled) = resolve(input, &actions);
+			let (fast, q_fast) = (resolve_fast(input, &actions, true), resolve_q_fast(input, &actions, true));
+			if fast != (plant_count, distance_traveled) || q_fast != fast
+			{
+				return Err(format!("{name}: resolve {:?}, resolve_fast {fast:?}, resolve_q_fast {q_fast:?}", (plant_count, distance_traveled)));
+			}
+		}
+
+		Ok(())
+	});
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use codingup_qualifs::greedy::nearest_neighbour;
+use codingup_qualifs::io::Input;
+use codingup_qualifs::tabu::tabu_search;
+use codingup_qualifs::{check_actions_at, score};
+use common::{check_cases, collect, is_valid_plan, plant, rng, random_input};
+
+
+#[test]
+fn check_actions_at_counts_the_plants_between_collects()
+{
+	let input = Input { seed_capacity: 2, ..Default::default() };
+	let actions = [plant([1, 0]), collect([0, 0]), plant([2, 0]), plant([3, 0]), plant([4, 0]), collect([0, 0]), plant([5, 0])];
+
+	assert!(check_actions_at(&input, &actions, 0));
+	assert!(check_actions_at(&input, &actions, 1));
+	assert!(!check_actions_at(&input, &actions, 3));
+	assert!(!check_actions_at(&input, &actions, 5));
+	assert!(check_actions_at(&input, &actions, 6));
+}
+
+#[test]
+fn crossing_routes_are_untangled()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 10,
+		range: 0,
+		plants: vec![[1, 0], [2, 0], [3, 0], [4, 0], [5, 0]],
+		..Default::default()
+	};
+
+	let actions = tabu_search(&input, &[plant([1, 0]), plant([4, 0]), plant([3, 0]), plant([2, 0]), plant([5, 0])], 10, 3);
+
+	assert_eq!(score(&input, &actions), (5, -5));
+}
+
+#[test]
+fn a_plant_left_behind_is_relocated()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 10,
+		range: 0,
+		plants: vec![[1, 0], [2, 0], [3, 0], [4, 0], [5, 0]],
+		..Default::default()
+	};
+
+	let actions = tabu_search(&input, &[plant([1, 0]), plant([3, 0]), plant([4, 0]), plant([5, 0]), plant([2, 0])], 10, 3);
+
+	assert_eq!(actions, vec![plant([1, 0]), plant([2, 0]), plant([3, 0]), plant([4, 0]), plant([5, 0])]);
+}
+
+#[test]
+fn search_keeps_the_seed_stock()
+{
+	check_cases("search_keeps_the_seed_stock", 60, |rng| random_input(rng, 10, 5), |input|
+	{
+		let initial = nearest_neighbour(input);
+		let actions = tabu_search(input, &initial, 20, 5);
+
+		if !is_valid_plan(input, &actions)
+		{
+			return Err(format!("tabu_search gave {actions:?}"));
+		}
+		if score(input, &actions) < score(input, &initial)
+		{
+			return Err(format!("{actions:?} is worse than {initial:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn search_is_deterministic()
+{
+	let input = random_input(&mut rng(3), 15, 6);
+	let initial = nearest_neighbour(&input);
+
+	assert_eq!(tabu_search(&input, &initial, 30, 7), tabu_search(&input, &initial, 30, 7));
+}
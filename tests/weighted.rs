@@ -0,0 +1,187 @@
+mod common;
+
+use codingup_qualifs::exact::solve_exact;
+use codingup_qualifs::greedy::{limited_search, nearest_neighbour};
+use codingup_qualifs::io::Input;
+use codingup_qualifs::realize::realize;
+use codingup_qualifs::{metric, planted_value, pruning, resolve, resolve_fast, resolve_q_fast, score, Action, ActionKind};
+use common::{check, check_cases, plant, random_input, PlanCase};
+
+
+// Values from 0 to 3, some plants are worth nothing
+fn with_values(input: &Input) -> Input
+{
+	Input
+	{
+		values: (0..input.plants.len() as u32).map(|index| (index * 5 + 3) % 4).collect(),
+		..input.clone()
+	}
+}
+
+
+#[test]
+fn values_are_parsed()
+{
+	let input: Input = serde_json::from_str(r#"{"maxDistance": 10, "seedCapacity": 1, "range": 0, "seeds": [], "plants": [[1, 2], [3, 4]], "values": [5, 7]}"#).unwrap();
+
+	assert_eq!(input.plant_value([3, 4]), 7);
+	assert_eq!(input.plant_value([0, 0]), 0);
+	assert_eq!(Input { values: Vec::new(), ..input }.plant_value([3, 4]), 1);
+}
+
+#[test]
+fn unit_values_change_nothing()
+{
+	check_cases("unit_values_change_nothing", 300, |rng| random_input(rng, 6, 3), |input|
+	{
+		let unit = Input { values: vec![1; input.plants.len()], ..input.clone() };
+		// Equal values do not change the lookahead either, even when a lookahead ends at a seed pile
+		let equal = Input { values: vec![2; input.plants.len()], ..input.clone() };
+
+		if nearest_neighbour(&unit) != nearest_neighbour(input)
+		{
+			return Err(format!("nearest_neighbour gave {:?} with unit values", nearest_neighbour(&unit)));
+		}
+
+		if limited_search(&unit, 3) != limited_search(input, 3) || limited_search(&equal, 3) != limited_search(input, 3)
+		{
+			return Err(format!("limited_search gave {:?} with unit values, {:?} with equal values", limited_search(&unit, 3), limited_search(&equal, 3)));
+		}
+
+		// With values, pruning drops plants instead of giving up when it cannot plant them all
+		let unit_score = score(&unit, &pruning::solve(&unit, unit.max_distance as i32));
+		let plain_score = score(input, &pruning::solve(input, input.max_distance as i32));
+		if unit_score < plain_score
+		{
+			return Err(format!("pruning gave {unit_score:?} with unit values, {plain_score:?} without"));
+		}
+
+		let (_, _, plant_count, distance_traveled) = solve_exact(input);
+		let (_, _, unit_plant_count, unit_distance_traveled) = solve_exact(&unit);
+		if (plant_count, distance_traveled) != (unit_plant_count, unit_distance_traveled)
+		{
+			return Err(format!("solve_exact gave {:?} with unit values, {:?} without", (unit_plant_count, unit_distance_traveled), (plant_count, distance_traveled)));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn pruning_searches_before_dropping_plants()
+{
+	// The spanning tree of the plants costs 13, a tour of 12 plants them all
+	let input = Input
+	{
+		max_distance: 12,
+		seed_capacity: 3,
+		range: 1,
+		plants: vec![[3, 1], [6, 5], [0, 4]],
+		values: vec![1, 1, 1],
+		..Default::default()
+	};
+
+	let actions = pruning::solve(&input, input.max_distance as i32);
+
+	assert_eq!(actions, vec![plant([3, 1]), plant([0, 4]), plant([6, 5])]);
+	assert_eq!(score(&input, &actions), (3, -12));
+}
+
+#[test]
+fn resolvers_agree_on_weighted_plans()
+{
+	check("resolvers_agree_on_weighted_plans", PlanCase::random, |case|
+	{
+		let input = with_values(&case.input);
+
+		let (_, plant_count, distance_traveled) = resolve(&input, &case.actions);
+		let fast = resolve_fast(&input, &case.actions, true);
+		let q_fast = resolve_q_fast(&input, &case.actions, true);
+		let (_, generic_plant_count, generic_distance_traveled) = metric::resolve(&input, &case.actions, true);
+		let realization = realize(&input, &case.actions);
+
+		let expected = (plant_count, distance_traveled);
+		let results = [fast, q_fast, (generic_plant_count, generic_distance_traveled), (realization.plant_count, realization.distance_traveled)];
+
+		if results.iter().any(|&result| result != expected)
+		{
+			return Err(format!("resolve {expected:?}, resolve_fast, resolve_q_fast, generic and realize {results:?}"));
+		}
+
+		if realization.value != planted_value(&input, &case.actions, plant_count)
+		{
+			return Err(format!("realization value {}", realization.value));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn solvers_never_beat_exact()
+{
+	check_cases("solvers_never_beat_exact", 300, |rng| with_values(&random_input(rng, 6, 3)), |input|
+	{
+		let (optimal_actions, _, _, _) = solve_exact(input);
+		let optimal_score = score(input, &optimal_actions);
+
+		let plans = [
+			("nearest_neighbour", nearest_neighbour(input)),
+			("limited_search", limited_search(input, 3)),
+			("pruning", pruning::solve(input, input.max_distance as i32)),
+		];
+
+		for (name, actions) in plans
+		{
+			let plan_score = score(input, &actions);
+			if plan_score > optimal_score
+			{
+				return Err(format!("{name} beats the exact solver with {plan_score:?}"));
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn pruning_drops_plants_worth_less_than_their_distance()
+{
+	let input = Input
+	{
+		max_distance: 25,
+		seed_capacity: 3,
+		range: 0,
+		plants: vec![[0, 2], [0, 4], [20, 0]],
+		values: vec![1, 1, 100],
+		..Default::default()
+	};
+
+	let actions = pruning::solve(&input, input.max_distance as i32);
+
+	assert!(actions.contains(&Action { pos: [20, 0], kind: ActionKind::Plant }), "{actions:?}");
+	assert_eq!(score(&input, &actions), (101, -24));
+}
+
+#[test]
+fn equal_values_rank_the_lookahead_by_distance()
+{
+	// Planting [8, 0] with the seed carried leaves no seed for [-1, 0], its lookahead ends there but it is the shortest
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 0,
+		seeds: vec![[10, 0]],
+		species: vec![1],
+		plants: vec![[8, 0], [-1, 0]],
+		requirements: vec![Vec::new(), vec![0]],
+		..Default::default()
+	};
+	let equal = Input { values: vec![2, 2], ..input.clone() };
+
+	let expected = vec![Action { pos: [8, 0], kind: ActionKind::Plant }];
+
+	assert_eq!(limited_search(&input, 3), expected);
+	assert_eq!(limited_search(&equal, 3), expected);
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use codingup_qualifs::io::Input;
+use codingup_qualifs::realize::realize;
+use codingup_qualifs::schedule::{earliest_deadline, start_times, timed_prefix};
+use codingup_qualifs::{resolve, resolve_fast, resolve_q_fast, ActionKind};
+use common::{check, check_cases, collect, is_valid_plan, plant, random_input, PlanCase};
+
+
+// Windows opening at 0 to 19 and staying open 15 to 24 time units
+fn with_windows(input: &Input) -> Input
+{
+	Input
+	{
+		windows: (0..input.plants.len() as u32).map(|index|
+		{
+			let open = index * 7 % 20;
+			[open, open + 15 + index * 3 % 10]
+		}).collect(),
+		plant_duration: 1,
+		collect_duration: 2,
+		..input.clone()
+	}
+}
+
+
+#[test]
+fn windows_are_parsed()
+{
+	let input: Input = serde_json::from_str(r#"{"maxDistance": 10, "seedCapacity": 1, "range": 0, "seeds": [], "plants": [[1, 2], [3, 4]], "windows": [[0, 5], [2, 9]], "plantDuration": 3, "collectDuration": 4}"#).unwrap();
+
+	assert_eq!(input.window([3, 4]), [2, 9]);
+	assert_eq!(input.window([0, 0]), [0, u32::MAX]);
+	assert_eq!((input.plant_duration, input.collect_duration), (3, 4));
+}
+
+#[test]
+fn robot_waits_for_the_window_and_misses_the_next_one()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 3,
+		range: 0,
+		plants: vec![[2, 0], [4, 0]],
+		windows: vec![[5, 10], [0, 7]],
+		plant_duration: 1,
+		..Default::default()
+	};
+
+	let actions = [plant([2, 0]), plant([4, 0])];
+
+	assert_eq!(start_times(&input, &actions), vec![5, 8]);
+	assert_eq!(timed_prefix(&input, &actions), 1);
+
+	let (_, plant_count, distance_traveled) = resolve(&input, &actions);
+	assert_eq!((plant_count, distance_traveled), (1, 2));
+	assert_eq!(resolve_fast(&input, &actions, true), (1, 2));
+	assert_eq!(resolve_q_fast(&input, &actions, true), (1, 2));
+
+	// The other order plants both
+	assert_eq!(resolve_fast(&input, &[plant([4, 0]), plant([2, 0])], true), (2, 6));
+}
+
+#[test]
+fn durations_and_range_shift_the_start_times()
+{
+	let input = Input
+	{
+		max_distance: 100,
+		seed_capacity: 1,
+		range: 1,
+		seeds: vec![[0, 3]],
+		plants: vec![[3, 0], [0, 6]],
+		plant_duration: 2,
+		collect_duration: 3,
+		..Default::default()
+	};
+
+	// 2 cells to the range of the first plant, 2 to plant, 5 cells to the seeds, 3 to collect and 2 cells
+	assert_eq!(start_times(&input, &[plant([3, 0]), collect([0, 3]), plant([0, 6])]), vec![2, 9, 14]);
+}
+
+#[test]
+fn earliest_deadline_plants_on_time()
+{
+	check_cases("earliest_deadline_plants_on_time", 200, |rng| with_windows(&random_input(rng, 8, 4)), |input|
+	{
+		let actions = earliest_deadline(input);
+
+		if !is_valid_plan(input, &actions) || timed_prefix(input, &actions) != actions.len()
+		{
+			return Err(format!("earliest_deadline gave {actions:?}"));
+		}
+
+		let plants = actions.iter().filter(|action| action.kind == ActionKind::Plant).count();
+		let (_, plant_count, distance_traveled) = resolve(input, &actions);
+		if plant_count != plants
+		{
+			return Err(format!("resolve plants {plant_count} of {actions:?}"));
+		}
+
+		let (fast, q_fast) = (resolve_fast(input, &actions, true), resolve_q_fast(input, &actions, true));
+		if fast != (plant_count, distance_traveled) || q_fast != fast
+		{
+			return Err(format!("resolve {:?}, resolve_fast {fast:?}, resolve_q_fast {q_fast:?}", (plant_count, distance_traveled)));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn resolvers_agree_with_windows()
+{
+	check("resolvers_agree_with_windows", PlanCase::random, |case|
+	{
+		let input = with_windows(&case.input);
+
+		let (_, plant_count, distance_traveled) = resolve(&input, &case.actions);
+		let realization = realize(&input, &case.actions);
+		let expected = (plant_count, distance_traveled);
+		let results = [resolve_fast(&input, &case.actions, true), resolve_q_fast(&input, &case.actions, true), (realization.plant_count, realization.distance_traveled)];
+
+		if results.iter().any(|&result| result != expected)
+		{
+			return Err(format!("resolve {expected:?}, resolve_fast, resolve_q_fast and realize {results:?}"));
+		}
+
+		Ok(())
+	});
+}
+
+#[test]
+fn start_times_go_around_obstacles()
+{
+	let input: Input = serde_json::from_str(r#"
+	{
+		"maxDistance": 100,
+		"seedCapacity": 2,
+		"range": 0,
+		"seeds": [],
+		"plants": [[2, 0], [6, 0]],
+		"windows": [[0, 10], [0, 15]],
+		"plantDuration": 1,
+		"obstacles": [{ "min": [4, -4], "max": [4, 4] }]
+	}"#).unwrap();
+
+	// 2 cells, 1 to plant, then 5 up, 4 across and 5 down around the wall
+	assert_eq!(start_times(&input, &[plant([2, 0]), plant([6, 0])]), vec![2, 17]);
+	assert_eq!(timed_prefix(&input, &[plant([2, 0]), plant([6, 0])]), 1);
+
+	// The second plant is missed whatever the order
+	assert_eq!(earliest_deadline(&input), vec![plant([2, 0])]);
+}